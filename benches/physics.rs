@@ -0,0 +1,109 @@
+//! Time of one physics step for a growing number of bodies.
+//!
+//! Run with `cargo bench --bench physics`.
+
+use bevy::{ecs::event::Events, prelude::*};
+use bounce_up::{
+    physics_systems, CollisionEvent, ContactEvent, Force, ForceField, Motion, PhysicsLayers,
+    RigidBody, Shape, TimeScale,
+};
+use std::time::{Duration, Instant};
+
+const BODY_COUNTS: [usize; 3] = [2, 50, 500];
+const WARMUP_STEPS: usize = 60;
+const STEPS: usize = 600;
+
+const ARENA_SIZE: Vec2 = Vec2::new(800.0, 600.0);
+const BALL_RADIUS: f32 = 8.0;
+const BALL_SPEED: f32 = 400.0;
+const BLOCK_SIZE: Vec2 = Vec2::new(48.0, 16.0);
+const WIND: Vec2 = Vec2::new(200.0, 0.0);
+const ATTRACTION: f32 = 300.0;
+
+/// A world with balls flying around and one static block for every ten bodies,
+/// with wind over the left half of the arena and an attractor in the middle.
+fn make_world(count: usize) -> World {
+    let rng = fastrand::Rng::with_seed(42);
+    let mut world = World::new();
+    world.init_resource::<TimeScale>();
+    world.init_resource::<Events<CollisionEvent>>();
+    world.init_resource::<Events<ContactEvent>>();
+
+    world.spawn((
+        Transform::from_xyz(-ARENA_SIZE.x / 4.0, 0.0, 0.0),
+        ForceField::new(
+            Force::Wind(WIND),
+            Shape::Aabb(ARENA_SIZE * Vec2::new(0.5, 1.0)),
+        ),
+    ));
+    world.spawn((
+        Transform::default(),
+        ForceField::new(Force::Radial(ATTRACTION), Shape::Circle(ARENA_SIZE.y / 4.0)),
+    ));
+
+    let position = || {
+        let position = Vec2::new(rng.f32(), rng.f32()) - 0.5;
+        (position * ARENA_SIZE).extend(0.0)
+    };
+
+    for index in 0..count {
+        if index % 10 == 9 {
+            world.spawn((
+                Transform::from_translation(position()),
+                RigidBody::new(Shape::Aabb(BLOCK_SIZE), 0.0, 0.9, 0.5),
+                PhysicsLayers::BOUNDARY,
+            ));
+        } else {
+            let angle = index as f32;
+            let translation = position();
+            world.spawn((
+                Transform::from_translation(translation),
+                RigidBody::new(Shape::Circle(BALL_RADIUS), 1.0, 1.0, 0.5),
+                Motion {
+                    velocity: BALL_SPEED * Vec2::new(angle.cos(), angle.sin()),
+                    translation,
+                    previous_translation: translation,
+                    ..Default::default()
+                },
+                PhysicsLayers::BALL,
+            ));
+        }
+    }
+
+    world
+}
+
+fn step(world: &mut World, stage: &mut SystemStage) -> Duration {
+    let start = Instant::now();
+    stage.run(world);
+    let elapsed = start.elapsed();
+
+    world.resource_mut::<Events<CollisionEvent>>().update();
+    world.resource_mut::<Events<ContactEvent>>().update();
+    elapsed
+}
+
+fn main() {
+    for count in BODY_COUNTS {
+        let mut world = make_world(count);
+        let mut stage = SystemStage::parallel().with_system_set(physics_systems());
+
+        for _ in 0..WARMUP_STEPS {
+            step(&mut world, &mut stage);
+        }
+
+        let mut total = Duration::ZERO;
+        let mut worst = Duration::ZERO;
+        for _ in 0..STEPS {
+            let elapsed = step(&mut world, &mut stage);
+            total += elapsed;
+            worst = worst.max(elapsed);
+        }
+
+        println!(
+            "{count:>4} bodies: {:>10.2?} per step, {:>10.2?} worst",
+            total / STEPS as u32,
+            worst
+        );
+    }
+}
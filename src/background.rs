@@ -60,7 +60,7 @@ fn update(
     time_scale: Res<TimeScale>,
     mut materials: ResMut<Assets<BackgroundMaterial>>,
 ) {
-    for (_, mut material) in materials.iter_mut() {
+    for (_, material) in materials.iter_mut() {
         material.time += time.delta_seconds() * time_scale.0;
     }
 }
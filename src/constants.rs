@@ -1,7 +1,17 @@
+use crate::utils::Shape;
 use bevy::{prelude::*, render::view::RenderLayers};
 
+pub const DATA_DIR_NAME: &str = "bounce-up";
+pub const WORD_LIST_FILE: &str = "word_list.tsv";
+pub const LEADERBOARD_FILE: &str = "leaderboard.tsv";
+pub const RIME_DICT_FILE: &str = "rime.dict.yaml";
+pub const RIME_SCHEMA_FILE: &str = "rime.schema.yaml";
+pub const DECKS_DIR: &str = "decks";
+
 pub const ARENA_WIDTH: f32 = 750.0;
 pub const ARENA_HEIGHT: f32 = 1000.0;
+pub const HORIZONTAL_BOUNDARY_SIZE: Vec2 = Vec2::new(ARENA_WIDTH, 32.0);
+pub const VERTICAL_BOUNDARY_SIZE: Vec2 = Vec2::new(32.0, ARENA_HEIGHT + 64.0);
 
 pub const DEATH_EFFECT_LAYER: RenderLayers = RenderLayers::layer(1);
 
@@ -44,20 +54,36 @@ pub const BUTTON_PRESSED_COLOR: Color = Color::WHITE;
 pub const SETTING_NORMAL_COLOR: Color = Color::BLACK;
 pub const SETTING_ACTIVE_COLOR: Color = Color::WHITE;
 pub const SETTING_HOVERED_COLOR: Color = Color::GRAY;
+pub const SETTING_TEXT_COLOR: Color = Color::rgb(0.851, 0.341, 0.388);
 
 pub const BUTTON_TEXT_NORMAL_COLOR: Color = Color::WHITE;
 pub const BUTTON_TEXT_HOVERED_COLOR: Color = Color::BLACK;
 pub const BUTTON_TEXT_PRESSED_COLOR: Color = Color::BLACK;
 
+pub const KEYBOARD_KEY_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.4);
+pub const KEYBOARD_NEXT_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.4);
+pub const KEYBOARD_CORRECT_COLOR: Color = Color::rgba(0.2, 0.8, 0.3, 0.8);
+pub const KEYBOARD_WRONG_COLOR: Color = Color::rgba(0.851, 0.341, 0.388, 0.8);
+
 pub const FLIP_TEXT_COLORS: [Color; 2] = [Color::WHITE, Color::GOLD];
 
+#[cfg(feature = "audio")]
 pub const MISS_AUDIO: &str = "audios/miss.flac";
+#[cfg(feature = "audio")]
 pub const EXPLOSION_AUDIO: &str = "audios/explosion.flac";
+#[cfg(feature = "audio")]
 pub const LOSE_AUDIO: &str = "audios/lose.flac";
+#[cfg(feature = "audio")]
 pub const HIT_AUDIO: &str = "audios/hit.ogg";
+#[cfg(feature = "audio")]
 pub const IMPACT_AUDIOS: [&str; 2] = ["audios/impacts/impact-1.ogg", "audios/impacts/impact-2.ogg"];
 
+#[cfg(feature = "audio")]
+pub const PRONUNCIATION_AUDIO_PATH: &str = "audios/syllables";
+
+#[cfg(feature = "audio")]
 pub const BUTTON_HOVER_AUDIO: &str = "audios/button/hover.ogg";
+#[cfg(feature = "audio")]
 pub const BUTTON_CLICK_AUDIO: &str = "audios/button/click.ogg";
 
 pub const MENU_MUSIC: &str = "musics/E2M2 Myrgharok - Halls of Wandering Spirits.ogg";
@@ -65,16 +91,73 @@ pub const GAME_MUSIC: &str = "musics/E3M8 Myrgharok - Mother of All Doom.ogg";
 
 pub const MENU_MUSIC_BPM: f32 = 102.0;
 
+pub const DICTATION_PLACEHOLDER: &str = "？";
+
+pub const SENTENCE_LENGTH: usize = 6;
+pub const SENTENCE_TOP: f32 = 48.0;
+pub const SENTENCE_ANSWERED_COLOR: Color = Color::GOLD;
+pub const SENTENCE_COVERED_COLOR: Color = Color::WHITE;
+pub const SENTENCE_PENDING_COLOR: Color = Color::GRAY;
+
+pub const KEYBOARD_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+pub const KEYBOARD_KEY_SIZE: f32 = 36.0;
+pub const KEYBOARD_FLASH_DURATION: f32 = 0.2;
+
+pub const INPUT_WARNING_DURATION: f32 = 5.0;
+
+pub const KEY_STATS_FILE_PREFIX: &str = "key_stats";
+pub const KEY_STATS_SLOW_DIGRAPHS: usize = 5;
+pub const FINGER_BIGRAMS_SHOWN: usize = 3;
+pub const KEY_HEAT_COLORS: [Color; 2] =
+    [Color::rgb(0.2, 0.6, 0.3), Color::rgb(0.851, 0.341, 0.388)];
+
+pub const DICTIONARY_FILE: &str = "cedict_ts.u8";
+pub const GLOSS_DURATION: f32 = 3.0;
+pub const GLOSS_MAX_DEFINITIONS: usize = 3;
+pub const GLOSS_KEYBOARD_OFFSET: f32 = 180.0;
+
+pub const PUNCTUATION_MIX_INTERVAL: usize = 4;
+
+pub const REVIEW_SLOW_ANSWER_DURATION: f32 = 5.0;
+pub const REVIEW_PAGE_SIZE: usize = 10;
+
+pub const SPRINT_DURATIONS: [u32; 3] = [60, 120, 300];
+pub const LEADERBOARD_SIZE: usize = 10;
+pub const LEADERBOARD_SHOWN: usize = 5;
+
+/// Characters in the longest word of a plain text word list; longer runs are split into characters.
+pub const WORD_MAX_LENGTH: usize = 4;
+pub const IMPORT_PANEL_DURATION: f32 = 5.0;
+pub const IMPORT_PANEL_MAX_UNKNOWN: usize = 20;
+/// Sizes of the character sets made of the most frequent characters.
+pub const FREQUENCY_SETS: [usize; 2] = [100, 250];
+pub const DECKS_SHOWN: usize = 4;
+/// Fuzzy spellings tried for a word, since each of its syllables multiplies them.
+pub const FUZZY_WORD_VARIANTS: usize = 64;
+pub const DECK_LABEL_LENGTH: usize = 8;
+
 pub const PREDICT_SIZE: usize = 100;
 pub const PREDICT_TIME_STEP: f32 = 0.01;
 pub const AI_TIME_STEP: f32 = 0.1;
 
 pub const PHYSICS_REST_SPEED: f32 = 100.0;
 pub const PHYSICS_TIME_STEP: f32 = 1.0 / 180.0;
+pub const PHYSICS_TIME_STEP_LABEL: &str = "physics";
+pub const PHYSICS_SOLVER_ITERATIONS: usize = 8;
+pub const PHYSICS_POSITION_ITERATIONS: usize = 3;
+/// Penetration depth left uncorrected, so resting contacts stay in touch.
+pub const PHYSICS_PENETRATION_SLOP: f32 = 0.5;
+/// Fraction of the penetration corrected in each position iteration.
+pub const PHYSICS_POSITION_CORRECTION: f32 = 0.8;
 
 pub const PADDLE_WIDTH: f32 = 96.0;
 pub const PADDLE_HEIGHT: f32 = 16.0;
 pub const BALL_SIZE: f32 = 20.0;
+/// Paddles are rounded at the ends, so corner hits deflect the ball smoothly.
+pub const PADDLE_SHAPE: Shape = Shape::Capsule {
+    half_length: (PADDLE_WIDTH - PADDLE_HEIGHT) / 2.0,
+    radius: PADDLE_HEIGHT / 2.0,
+};
 
 pub const PLAYER_MAX_SPEED: f32 = 2000.0;
 pub const PLAYER_SENSITIVITY: f32 = 0.5;
@@ -104,8 +187,13 @@ pub const MAX_DAMAGE: f32 = 2000.0;
 
 pub const BALL_GHOSTS_COUNT: usize = 16;
 pub const BALL_MAX_SPEED: f32 = 3000.0;
+pub const BALL_MAX_SPIN: f32 = 30.0;
+pub const BALL_SPIN_DAMPING: f32 = 0.5;
+pub const BALL_MAGNUS: f32 = 0.025;
 
+#[cfg(feature = "audio")]
 pub const MIN_BOUNCE_AUDIO_SPEED: f32 = 500.0;
+#[cfg(feature = "audio")]
 pub const MAX_BOUNCE_AUDIO_SPEED: f32 = 2500.0;
 pub const MAX_BOUNCE_EFFECTS_SPEED: f32 = 2500.0;
 
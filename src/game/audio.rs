@@ -0,0 +1,257 @@
+use super::*;
+use crate::{utils::Intermediate, AudioVolume};
+use bevy::asset::LoadState;
+use bevy_kira_audio::{
+    AudioApp, AudioChannel, AudioControl, AudioInstance, AudioSource, PlaybackState,
+};
+use std::collections::VecDeque;
+
+/// Sound effects of the game: bounces, scores and the pronunciation of answered glyphs.
+pub struct GameAudioPlugin;
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PronounceEvent>()
+            .init_resource::<AudioDebounce>()
+            .add_audio_channel::<BounceAudioChannel>()
+            .add_audio_channel::<ScoreAudioChannel>()
+            .add_audio_channel::<PronunciationAudioChannel>()
+            .add_startup_system(load_audios)
+            .add_system(bounce_audio)
+            .add_system(score_audio)
+            .add_system(pronounce_glyph.after(check_answer))
+            .add_system(
+                pronunciation_audio
+                    .after(pronounce_glyph)
+                    .before(advance_glyph),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct BounceAudioChannel;
+
+#[derive(Resource)]
+struct ScoreAudioChannel;
+
+#[derive(Resource)]
+struct PronunciationAudioChannel;
+
+#[derive(Resource)]
+struct AudioDebounce {
+    bounce_long: Timer,
+    bounce_short: Timer,
+    hit: Timer,
+}
+
+impl Default for AudioDebounce {
+    fn default() -> Self {
+        Self {
+            bounce_long: Timer::from_seconds(0.5, TimerMode::Once),
+            bounce_short: Timer::from_seconds(0.1, TimerMode::Once),
+            hit: Timer::from_seconds(0.1, TimerMode::Once),
+        }
+    }
+}
+
+#[derive(Resource)]
+struct Audios {
+    hit_audio: Handle<AudioSource>,
+    miss_audio: Handle<AudioSource>,
+    explosion_audio: Handle<AudioSource>,
+    lose_audio: Handle<AudioSource>,
+    impact_audios: Vec<Handle<AudioSource>>,
+}
+
+fn load_audios(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Audios {
+        hit_audio: asset_server.load(HIT_AUDIO),
+        miss_audio: asset_server.load(MISS_AUDIO),
+        explosion_audio: asset_server.load(EXPLOSION_AUDIO),
+        lose_audio: asset_server.load(LOSE_AUDIO),
+        impact_audios: IMPACT_AUDIOS
+            .iter()
+            .map(|path| asset_server.load(*path))
+            .collect_vec(),
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn bounce_audio(
+    audio: Res<AudioChannel<BounceAudioChannel>>,
+    audios: Res<Audios>,
+    volume: Res<AudioVolume>,
+    time: Res<Time>,
+    mut timer: ResMut<AudioDebounce>,
+    mut events: EventReader<CollisionEvent>,
+    mut bounce_entities: Local<Option<[Entity; 2]>>,
+    query: Query<(Entity, &BounceAudio)>,
+    balls: Query<(), With<Ball>>,
+    motions: Query<Option<&Motion>>,
+) {
+    let mut can_play_audio = timer.bounce_long.tick(time.delta()).finished();
+    timer.bounce_short.tick(time.delta());
+    timer.hit.tick(time.delta());
+
+    for event in events.iter() {
+        // one of the entities must be a ball
+        let results = event.entities.map(|entity| balls.get(entity).is_ok());
+        if !results.contains(&true) {
+            continue;
+        }
+
+        let (entities, bounce_audio) = if let Ok(x) = query.get_many(event.entities) {
+            let (entities, bounce_audios): (Vec<_>, Vec<_>) = x.iter().cloned().unzip();
+            let bounce_audio = if bounce_audios.contains(&BounceAudio::Hit) {
+                BounceAudio::Hit
+            } else {
+                BounceAudio::Bounce
+            };
+            (entities.try_into().ok(), bounce_audio)
+        } else {
+            continue;
+        };
+
+        let (audio_source, debounce_timer) = match bounce_audio {
+            BounceAudio::Bounce => {
+                let index = fastrand::usize(..IMPACT_AUDIOS.len());
+                (audios.impact_audios[index].clone(), &timer.bounce_short)
+            }
+            BounceAudio::Hit => (audios.hit_audio.clone(), &timer.hit),
+        };
+
+        if entities != *bounce_entities {
+            can_play_audio = debounce_timer.finished();
+            *bounce_entities = entities;
+        }
+
+        if can_play_audio {
+            let velocities = motions
+                .many(event.entities)
+                .map(|maybe_motion| maybe_motion.map_or(Vec2::ZERO, |motion| motion.velocity));
+            let speed = (velocities[0] - velocities[1]).length();
+            if speed > MIN_BOUNCE_AUDIO_SPEED {
+                let normalized_speed = speed
+                    .intermediate(MIN_BOUNCE_AUDIO_SPEED, MAX_BOUNCE_AUDIO_SPEED)
+                    .clamp(0.0, 1.0);
+
+                let panning = event.hit.location().x / ARENA_WIDTH + 0.5;
+                let volume = volume.effects * (0.5 * normalized_speed + 0.5);
+                let playback_rate = 0.4 * fastrand::f32() + 0.8;
+                audio
+                    .play(audio_source)
+                    .with_volume(volume.into())
+                    .with_panning(panning.into())
+                    .with_playback_rate(playback_rate.into());
+
+                timer.bounce_long.reset();
+                timer.bounce_short.reset();
+            }
+        }
+    }
+}
+
+fn score_audio(
+    audio: Res<AudioChannel<ScoreAudioChannel>>,
+    audios: Res<Audios>,
+    volume: Res<AudioVolume>,
+    mut player_miss_events: EventReader<PlayerMissEvent>,
+    mut game_over_events: EventReader<GameOverEvent>,
+) {
+    for event in player_miss_events.iter() {
+        let panning = event.location.x / ARENA_WIDTH + 0.5;
+        audio
+            .play(audios.miss_audio.clone())
+            .with_volume(volume.effects.into())
+            .with_panning(panning.into());
+    }
+
+    for event in game_over_events.iter() {
+        let audio_source = match event {
+            GameOverEvent::Win => audios.explosion_audio.clone(),
+            GameOverEvent::Lose => audios.lose_audio.clone(),
+        };
+        audio.play(audio_source).with_volume(volume.effects.into());
+    }
+}
+
+/// Pronounces a glyph once it's answered correctly,
+/// or the one in play nearest to the player on demand with [`KeyCode::F1`].
+fn pronounce_glyph(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut answer_events: EventReader<AnswerEvent>,
+    mut pronounce_events: EventWriter<PronounceEvent>,
+    players: Query<&Motion, With<Player>>,
+    query: Query<(Entity, &Motion), With<Glyph>>,
+) {
+    for event in answer_events.iter() {
+        if event.correct {
+            pronounce_events.send(PronounceEvent { ball: event.ball });
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::F1) {
+        let player = players
+            .get_single()
+            .map_or(Vec3::ZERO, |motion| motion.translation);
+        let nearest = query.iter().min_by(|(_, motion), (_, other)| {
+            let distance = motion.translation.distance_squared(player);
+            distance.total_cmp(&other.translation.distance_squared(player))
+        });
+        if let Some((ball, _)) = nearest {
+            pronounce_events.send(PronounceEvent { ball });
+        }
+    }
+}
+
+/// Plays the syllable recordings of pronounced glyphs one after another, so none cuts another off.
+/// Unlike bounce audio this is never debounced, so every answer gets its reading.
+/// Syllables without a recording under [`PRONUNCIATION_AUDIO_PATH`] are skipped.
+fn pronunciation_audio(
+    audio: Res<AudioChannel<PronunciationAudioChannel>>,
+    asset_server: Res<AssetServer>,
+    volume: Res<AudioVolume>,
+    mut events: EventReader<PronounceEvent>,
+    mut queue: Local<VecDeque<Handle<AudioSource>>>,
+    mut playing: Local<Option<Handle<AudioInstance>>>,
+    query: Query<&Glyph>,
+) {
+    // the reading is taken now, before the glyph of the ball may change
+    for event in events.iter() {
+        // punctuation has no reading
+        if let Some(glyph) = query
+            .get(event.ball)
+            .ok()
+            .filter(|glyph| !glyph.reading.is_empty())
+        {
+            queue.extend(glyph.audio_paths().map(|path| asset_server.load(path)));
+        }
+    }
+
+    let finished = playing
+        .as_ref()
+        .is_none_or(|instance| matches!(audio.state(instance), PlaybackState::Stopped));
+    if !finished {
+        return;
+    }
+
+    // a recording is only played once loaded, since one that fails would never stop
+    while let Some(source) = queue.front() {
+        match asset_server.get_load_state(source) {
+            LoadState::Loaded => {
+                let source = queue.pop_front().unwrap();
+                *playing = Some(
+                    audio
+                        .play(source)
+                        .with_volume(volume.pronunciation.into())
+                        .handle(),
+                );
+                break;
+            }
+            LoadState::Failed | LoadState::Unloaded => {
+                queue.pop_front();
+            }
+            LoadState::NotLoaded | LoadState::Loading => break,
+        }
+    }
+}
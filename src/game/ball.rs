@@ -1,4 +1,12 @@
-use super::physics::{Motion, RigidBody};
+#[cfg(feature = "audio")]
+use super::glyph::PronounceEvent;
+use super::{
+    base::PlayerBase,
+    field::{field_acceleration, ForceField},
+    physics::{self, resolve, Contact, Motion, PhysicsLayers, RigidBody, SolverBody},
+    Damp, TypingMode,
+};
+use crate::utils::{collide, Collider};
 use crate::{constants::*, TimeScale};
 use bevy::prelude::*;
 use std::f32::consts::FRAC_PI_2;
@@ -20,20 +28,44 @@ impl Default for Ball {
     }
 }
 
+impl Ball {
+    /// Acceleration in flight: gravity, and the Magnus force of the spin, across the velocity.
+    pub fn acceleration(&self, velocity: Vec2, angular_velocity: f32) -> Vec2 {
+        Vec2::new(0.0, self.gravity) + BALL_MAGNUS * angular_velocity * velocity.perp()
+    }
+
+    /// Spin after some time in flight, slowing down with air drag.
+    pub fn spin(&self, angular_velocity: f32, delta_seconds: f32) -> f32 {
+        angular_velocity
+            .damp(0.0, BALL_SPIN_DAMPING, delta_seconds)
+            .clamp(-BALL_MAX_SPIN, BALL_MAX_SPIN)
+    }
+}
+
 /// For an unset ball without [`Motion`], moves it to origin and makes it movable after some time.
+/// In [`TypingMode::Dictation`] the glyph is hidden and pronounced as the ball becomes active.
 pub fn activate_ball(
     mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Ball, &mut Transform), Without<Motion>>,
+    typing_mode: Res<TypingMode>,
+    #[cfg(feature = "audio")] mut pronounce_events: EventWriter<PronounceEvent>,
+    mut query: Query<(Entity, &mut Ball, &mut Transform, &mut Text), Without<Motion>>,
 ) {
-    for (entity, mut ball, mut transform) in query.iter_mut() {
+    for (entity, mut ball, mut transform, mut text) in query.iter_mut() {
         if ball.set_timer.tick(time.delta()).just_finished() {
             transform.translation = Vec3::ZERO;
+            transform.rotation = Quat::IDENTITY;
         }
 
         if ball.active_timer.tick(time.delta()).just_finished() {
             commands.entity(entity).insert(Motion::default());
 
+            if *typing_mode == TypingMode::Dictation {
+                text.sections[0].value = DICTATION_PLACEHOLDER.into();
+                #[cfg(feature = "audio")]
+                pronounce_events.send(PronounceEvent { ball: entity });
+            }
+
             // reset the ball timers
             ball.set_timer.reset();
             ball.active_timer.reset();
@@ -43,10 +75,10 @@ pub fn activate_ball(
 
 /// Implements motion blur using a bunch of transparent ghost sprites.
 pub fn update_ball(
-    ball_query: Query<(&Children, Option<&Motion>), With<Ball>>,
+    ball_query: Query<(&Children, &Transform, Option<&Motion>), With<Ball>>,
     mut child_query: Query<&mut Transform, Without<Ball>>,
 ) {
-    for (children, motion) in ball_query.iter() {
+    for (children, ball_transform, motion) in ball_query.iter() {
         for (index, child) in children.iter().enumerate() {
             let extent = 2.0 * BALL_SIZE;
             let count = (BALL_GHOSTS_COUNT) as f32;
@@ -56,8 +88,9 @@ pub fn update_ball(
 
             let mut transform = child_query.get_mut(*child).unwrap();
             if let Some(motion) = motion {
-                transform.translation =
-                    (extent * offset * motion.velocity / BALL_MAX_SPEED).extend(0.0);
+                // ghosts trail along the velocity however the glyph is spun
+                transform.translation = ball_transform.rotation.inverse()
+                    * (extent * offset * motion.velocity / BALL_MAX_SPEED).extend(0.0);
             } else {
                 transform.translation = Vec3::ZERO;
             }
@@ -71,7 +104,10 @@ pub fn move_ball(
     mut query: Query<(&Ball, &mut Motion)>,
 ) {
     for (ball, mut motion) in query.iter_mut() {
-        motion.velocity.y += ball.gravity * time.delta_seconds() * time_scale.0;
+        let delta_seconds = time.delta_seconds() * time_scale.0;
+        let acceleration = ball.acceleration(motion.velocity, motion.angular_velocity);
+        motion.velocity += acceleration * delta_seconds;
+        motion.angular_velocity = ball.spin(motion.angular_velocity, delta_seconds);
 
         let speed = motion.velocity.length();
         if speed > BALL_MAX_SPEED {
@@ -80,11 +116,24 @@ pub fn move_ball(
     }
 }
 
+/// Turns the glyph with the spin of the ball.
+pub fn spin_ball(
+    time: Res<Time>,
+    time_scale: Res<TimeScale>,
+    mut query: Query<(&Motion, &mut Transform), With<Ball>>,
+) {
+    for (motion, mut transform) in query.iter_mut() {
+        transform.rotate_z(motion.angular_velocity * time.delta_seconds() * time_scale.0);
+    }
+}
+
 #[derive(Default, Clone, Copy)]
 pub struct Point {
     pub position: Vec2,
     pub velocity: Vec2,
     pub time: f32,
+    /// The collider the ball bounced off in the step to this point.
+    pub bounce: Option<Entity>,
 }
 
 #[derive(Component)]
@@ -93,59 +142,152 @@ pub struct Trajectory {
     pub points: Vec<Point>,
 }
 
+impl Trajectory {
+    /// The predicted points before the ball bounces off a player base.
+    pub fn in_play(&self, bases: &Query<(), With<PlayerBase>>) -> &[Point] {
+        let end = self
+            .points
+            .iter()
+            .position(|point| point.bounce.is_some_and(|entity| bases.contains(entity)))
+            .unwrap_or(self.points.len());
+        &self.points[..end]
+    }
+}
+
 impl Default for Trajectory {
     fn default() -> Self {
         Self {
             start_time: 0.0,
-            points: vec![Point::default(); PREDICT_SIZE],
+            points: Vec::with_capacity(PREDICT_SIZE),
         }
     }
 }
 
+/// Predicts the path of the ball against every static collider it can hit, resolving the contacts as
+/// the physics does. The path ends early where the ball reaches the player base, as the ball is lost there.
+#[allow(clippy::type_complexity)]
 pub fn predict_ball(
     time: Res<Time>,
-    mut query: Query<(&Ball, &RigidBody, &Motion, &mut Trajectory)>,
+    mut query: Query<(
+        Entity,
+        &Ball,
+        &RigidBody,
+        &Motion,
+        &PhysicsLayers,
+        &mut Trajectory,
+    )>,
+    colliders: Query<
+        (
+            Entity,
+            &RigidBody,
+            &Transform,
+            &PhysicsLayers,
+            Option<&PlayerBase>,
+        ),
+        Without<Motion>,
+    >,
+    fields: Query<(&ForceField, &Transform)>,
 ) {
-    for (ball, rigid_body, motion, mut trajectory) in query.iter_mut() {
+    for (entity, ball, rigid_body, motion, layers, mut trajectory) in query.iter_mut() {
         let start_time = time.elapsed_seconds();
-        let boundary = (Vec2::new(ARENA_WIDTH, ARENA_HEIGHT) + rigid_body.size) / 2.0;
+        let obstacles = colliders
+            .iter()
+            .filter(|(_, body, _, other, _)| {
+                !body.sensor && layers.collision.intersects(&other.collision)
+            })
+            .collect::<Vec<_>>();
 
         let mut position = motion.translation.truncate();
         let mut velocity = motion.velocity;
+        let mut angular_velocity = motion.angular_velocity;
         let mut time = 0.0;
 
-        if let Some(point) = trajectory.points.first_mut() {
-            *point = Point {
-                position,
-                velocity,
-                time,
-            };
-        }
+        // the ball comes first, followed by the obstacles, which never move
+        let mut bodies = vec![SolverBody::moving(rigid_body, velocity, angular_velocity)];
+        bodies.extend(
+            obstacles
+                .iter()
+                .map(|(_, body, ..)| SolverBody::fixed(body)),
+        );
+        let mut colliders = vec![Collider {
+            previous_position: position,
+            position,
+            shape: rigid_body.shape,
+        }];
+        colliders.extend(
+            obstacles
+                .iter()
+                .map(|(_, body, transform, ..)| physics::collider(body, transform, None)),
+        );
 
         trajectory.start_time = start_time;
-        for point in trajectory.points.iter_mut().skip(1) {
-            velocity.y += ball.gravity * PREDICT_TIME_STEP;
+        trajectory.points.clear();
+        trajectory.points.push(Point {
+            position,
+            velocity,
+            time,
+            bounce: None,
+        });
+
+        let mut lost = false;
+        while !lost && trajectory.points.len() < PREDICT_SIZE {
+            let previous_position = position;
+            let gravity = Vec2::new(0.0, ball.gravity);
+            let acceleration = ball.acceleration(velocity, angular_velocity)
+                + field_acceleration(&fields, position, rigid_body.inverted_mass, gravity);
+            velocity += acceleration * PREDICT_TIME_STEP;
+            angular_velocity = ball.spin(angular_velocity, PREDICT_TIME_STEP);
             position += velocity * PREDICT_TIME_STEP;
+            time += PREDICT_TIME_STEP;
 
-            if position.x.abs() > boundary.x {
-                velocity.x *= -rigid_body.bounciness;
-                velocity.y *= rigid_body.friction;
-                position.x = position.x.clamp(-boundary.x + 0.01, boundary.x - 0.01);
-            }
+            bodies[0] = SolverBody::moving(rigid_body, velocity, angular_velocity);
+            colliders[0].previous_position = previous_position;
+            colliders[0].position = position;
 
-            if position.y > boundary.y {
-                velocity.y *= -rigid_body.bounciness;
-                velocity.x *= rigid_body.friction;
-                position.y = position.y.clamp(-boundary.y + 0.01, boundary.y - 0.01);
+            let mut contacts = vec![];
+            let mut hits = vec![];
+            for (index, (other, other_body, _, other_layers, base)) in obstacles.iter().enumerate()
+            {
+                let other_index = index + 1;
+                let hit = match collide(&colliders[0], &colliders[other_index]) {
+                    Some(hit) => hit,
+                    None => continue,
+                };
+                let mut entities = [entity, *other];
+                entities.sort();
+                if let Some(contact) = Contact::new(
+                    [0, other_index],
+                    entities,
+                    [rigid_body, other_body],
+                    [layers, other_layers],
+                    &bodies,
+                    hit,
+                    false,
+                ) {
+                    contacts.push(contact);
+                    hits.push((*other, base.is_some()));
+                }
             }
+            resolve(&mut contacts, &mut bodies, &colliders);
 
-            time += PREDICT_TIME_STEP;
+            velocity = bodies[0].velocity;
+            angular_velocity = bodies[0].angular_velocity;
+            position += bodies[0].correction;
+
+            // a bounce off the player base is kept over any other, as the ball is lost there
+            lost = hits.iter().any(|&(_, base)| base);
+            let bounce = hits
+                .iter()
+                .filter(|&&(_, base)| base == lost)
+                .map(|&(entity, _)| entity)
+                .next();
 
-            *point = Point {
+            trajectory.points.push(Point {
                 position,
                 velocity,
                 time,
-            };
+                bounce,
+            });
         }
     }
 }
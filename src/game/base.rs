@@ -127,7 +127,7 @@ pub fn heal_enemy_base(
 
         if heal
             .as_mut()
-            .map_or(false, |heal| heal.timer.tick(time.delta()).finished())
+            .is_some_and(|heal| heal.timer.tick(time.delta()).finished())
             || base.hp >= base.full_hp
         {
             *heal = None;
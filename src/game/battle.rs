@@ -9,18 +9,15 @@ impl Plugin for BattlePlugin {
                 .with_system(enter_battle)
                 .with_system(make_arena)
                 .with_system(make_ui)
+                .with_system(make_keyboard)
                 .with_system(make_player)
                 .with_system(make_enemy)
                 .with_system(make_ball),
         )
         .add_system_set(
-            SystemSet::on_update(AppState::Battle)
-                // logical game-play systems
+            battle_systems()
+                .with_run_criteria(State::on_update(AppState::Battle))
                 .with_system(escape_system)
-                .with_system(reset_ball)
-                .with_system(remove_ball)
-                .with_system(player_hit)
-                .with_system(player_miss)
                 .with_system(game_over_system),
         )
         .add_system_set(
@@ -29,32 +26,43 @@ impl Plugin for BattlePlugin {
     }
 }
 
+/// Balls of a battle, lost for good once they pass the player, shared with [`simulate`].
+pub fn battle_systems() -> SystemSet {
+    SystemSet::new()
+        .after(score_system)
+        .with_system(reset_ball)
+        .with_system(remove_ball.after(reset_ball))
+        .with_system(player_hit.after(remove_ball))
+        .with_system(player_miss.after(player_hit))
+}
+
 #[allow(clippy::too_many_arguments)]
 fn enter_battle(
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    volume: Res<AudioVolume>,
     mut music_track: ResMut<MusicTrack>,
     time: Res<Time>,
     mut time_scale: ResMut<TimeScale>,
     mut score: ResMut<Score>,
+    mut review: ResMut<Review>,
+    mut finger_stats: ResMut<FingerStats>,
+    mut last_keystroke: ResMut<LastKeystroke>,
+    mut sprint: ResMut<Sprint>,
     mut heal_events: EventWriter<HealEvent>,
 ) {
     // clear score state
     score.timestamp = time.elapsed_seconds();
     score.hits = 0;
     score.miss = 0;
+    score.answers = 0;
+    review.mistakes.clear();
+    *finger_stats = FingerStats::default();
+    last_keystroke.0 = None;
+    sprint.result = None;
 
     time_scale.reset();
 
     heal_events.send(HealEvent(Heal::default()));
 
     if music_track.0 != GAME_MUSIC {
-        audio.stop();
-        audio.set_volume(volume.music.into());
-        audio.set_playback_rate(1.2);
-        audio.play(asset_server.load(GAME_MUSIC)).looped();
-
         music_track.0 = GAME_MUSIC;
     }
 }
@@ -0,0 +1,616 @@
+use super::{
+    glyph::{Glyph, GlyphTable},
+    word_list::{
+        parse_word_list, spawn_import_panel, split_fields, word_list_tsv, DroppedFile,
+        ImportFormat, ImportPanel, ParsedWordList, WordListFormat,
+    },
+};
+use crate::{constants::*, utils::data_dir};
+use bevy::prelude::*;
+use itertools::Itertools;
+use std::{collections::HashSet, fs, io, path::Path, sync::OnceLock};
+
+/// A flashcard export, told apart from a plain text word list by its contents.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DeckFormat {
+    /// An Anki "Notes in Plain Text" export, with `#separator:` and similar headers.
+    Anki,
+    /// A Pleco text export: headword, pinyin and definition separated by tabs.
+    /// Also covers Anki exports from before the headers were written.
+    PlecoText,
+    /// A Pleco XML export.
+    PlecoXml,
+}
+
+impl DeckFormat {
+    /// Whether a file may be a deck by its extension, so its text is worth reading.
+    pub fn may_be(path: &Path) -> bool {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                matches!(extension.to_ascii_lowercase().as_str(), "txt" | "xml")
+            })
+    }
+
+    /// Tells a deck by its extension, and a text file by whether its lines are tab-separated cards.
+    pub fn detect(path: &Path, text: &str) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "xml" => Some(Self::PlecoXml),
+            "txt" => Self::detect_text(text),
+            _ => None,
+        }
+    }
+
+    fn detect_text(text: &str) -> Option<Self> {
+        let mut lines = text
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .peekable();
+        if lines.peek()?.starts_with("#separator:") || lines.peek()?.starts_with("#html:") {
+            return Some(Self::Anki);
+        }
+        // at least one card, besides Pleco category headers
+        let mut cards = lines
+            .filter(|line| !line.starts_with("//") && !line.starts_with('#'))
+            .peekable();
+        let is_card = cards.peek().is_some();
+        (is_card && cards.all(|line| line.contains('\t'))).then_some(Self::PlecoText)
+    }
+}
+
+/// Tone-marked vowels by tone, with the letter typed for them.
+const TONE_MARKS: [(char, [char; 4]); 6] = [
+    ('a', ['ā', 'á', 'ǎ', 'à']),
+    ('e', ['ē', 'é', 'ě', 'è']),
+    ('i', ['ī', 'í', 'ǐ', 'ì']),
+    ('o', ['ō', 'ó', 'ǒ', 'ò']),
+    ('u', ['ū', 'ú', 'ǔ', 'ù']),
+    ('v', ['ǖ', 'ǘ', 'ǚ', 'ǜ']),
+];
+
+/// Number of syllables toneless pinyin splits into, longest syllables first, e.g. 2 for `zhongguo`;
+/// `None` unless it's made of whole syllables.
+fn count_syllables(letters: &str) -> Option<usize> {
+    static SYLLABLES: OnceLock<HashSet<&str>> = OnceLock::new();
+    let syllables = SYLLABLES.get_or_init(|| {
+        include_str!("syllables.txt")
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .flat_map(str::split_whitespace)
+            .collect()
+    });
+
+    // syllables in the rest of the letters from each index on, filled in from the end
+    let mut counts = vec![None; letters.len() + 1];
+    counts[letters.len()] = Some(0);
+    for start in (0..letters.len()).rev() {
+        // the longest syllables, such as `zhuang`, have 6 letters
+        counts[start] = (1..=(letters.len() - start).min(6))
+            .rev()
+            .find_map(|length| {
+                let syllable = letters.get(start..start + length)?;
+                match syllables.contains(syllable) {
+                    true => counts[start + length].map(|count| count + 1),
+                    false => None,
+                }
+            });
+    }
+    counts[0]
+}
+
+/// Toneless pinyin code and reading of a pinyin field, e.g. `zhong` and `zhong1` for `zhōng`.
+/// Only fields of whole syllables with tones count as pinyin, so a definition like `China`,
+/// `a 3-day trip` or `café` is never taken for one.
+/// The reading is empty for words, which have no single syllable recording.
+fn parse_pinyin(field: &str) -> Option<(String, String)> {
+    let mut code = String::new();
+    let mut tones = vec![];
+    let mut syllables = 0;
+    // letters up to a tone number or separator, and the tone marks among them
+    let mut letters = String::new();
+    let mut marks = 0;
+
+    let field = field.trim().to_lowercase().replace("u:", "v");
+    for char in field.chars().chain([' ']) {
+        match char {
+            'a'..='z' => letters.push(char),
+            'ü' => letters.push('v'),
+            '1'..='5' | ' ' | '\'' | '-' => {
+                let tone = char.to_digit(10);
+                if letters.is_empty() {
+                    // a tone number ends a syllable, so it can't follow a separator
+                    match tone {
+                        Some(_) => return None,
+                        None => continue,
+                    }
+                }
+                let count = count_syllables(&letters)?;
+                if marks + usize::from(tone.is_some()) > count {
+                    return None;
+                }
+                tones.extend(tone);
+                syllables += count;
+                code.push_str(&letters);
+                letters.clear();
+                marks = 0;
+            }
+            _ => {
+                let (letter, tone) = TONE_MARKS.iter().find_map(|(letter, marks)| {
+                    Some((*letter, marks.iter().position(|mark| *mark == char)?))
+                })?;
+                letters.push(letter);
+                marks += 1;
+                tones.push(tone as u32 + 1);
+            }
+        }
+    }
+
+    if code.is_empty() || tones.is_empty() {
+        return None;
+    }
+    let reading = match (syllables, tones.as_slice()) {
+        (1, [tone]) => format!("{code}{tone}"),
+        _ => String::new(),
+    };
+    Some((code, reading))
+}
+
+/// Removes HTML tags and the entities Anki and Pleco write.
+fn strip_html(field: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for char in field.chars() {
+        match char {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(char),
+            _ => {}
+        }
+    }
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+/// Simplified headword of a card, dropping the traditional form Pleco adds in brackets.
+fn headword(field: &str) -> String {
+    let field = strip_html(field);
+    match field.split_once(['[', '［']) {
+        Some((simplified, _)) => simplified.trim().into(),
+        None => field,
+    }
+}
+
+/// Makes the glyph of a card, looking up the code of a single character when the card has no pinyin.
+fn card_glyph(
+    headword: &str,
+    pinyin: Option<&str>,
+    table: &GlyphTable,
+    unknown: &mut Vec<String>,
+) -> Option<Glyph> {
+    if headword.is_empty() {
+        return None;
+    }
+    match pinyin.and_then(parse_pinyin) {
+        Some((code, reading)) => {
+            let reading = match reading.is_empty() {
+                true => table
+                    .get(headword)
+                    .map(|glyph| glyph.reading.clone())
+                    .unwrap_or_default(),
+                false => reading,
+            };
+            Some(Glyph::new(headword, &reading, &[&code]))
+        }
+        None => {
+            let glyph = table.get(headword).cloned();
+            if glyph.is_none() {
+                unknown.push(headword.into());
+            }
+            glyph
+        }
+    }
+}
+
+/// Parses cards separated by lines, with the headword in the first field that holds a note.
+fn parse_text_cards(text: &str, table: &GlyphTable) -> ParsedWordList {
+    let mut separator = '\t';
+    // Anki puts the note type, deck and tags in their own columns, counted from 1
+    let mut meta_columns = vec![];
+    let mut pinyin_column = None;
+    let mut glyphs = vec![];
+    let mut unknown = vec![];
+
+    for line in text.trim_start_matches('\u{feff}').lines() {
+        if let Some(header) = line.strip_prefix('#') {
+            let (key, value) = header.split_once(':').unwrap_or((header, ""));
+            match key.trim() {
+                "separator" => {
+                    separator = match value.trim().to_lowercase().as_str() {
+                        "comma" | "," => ',',
+                        "semicolon" | ";" => ';',
+                        "pipe" | "|" => '|',
+                        "space" | " " => ' ',
+                        _ => '\t',
+                    }
+                }
+                "columns" => {
+                    pinyin_column = split_fields(value, separator).iter().position(|column| {
+                        let column = column.to_lowercase();
+                        column.contains("pinyin") || column.contains("reading")
+                    })
+                }
+                key if key.ends_with(" column") => {
+                    if let Some(column) = value.trim().parse::<usize>().ok().filter(|c| *c > 0) {
+                        meta_columns.push(column - 1);
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        // Pleco category headers
+        if line.trim().is_empty() || line.starts_with("//") {
+            continue;
+        }
+
+        // Anki quotes fields that contain the separator
+        let fields = split_fields(line, separator);
+        let notes = fields
+            .iter()
+            .enumerate()
+            .filter(|(column, _)| !meta_columns.contains(column))
+            .map(|(_, field)| field.as_str())
+            .collect_vec();
+        let headword = headword(notes.first().copied().unwrap_or_default());
+        let pinyin = match pinyin_column {
+            Some(column) => fields.get(column).map(|field| strip_html(field)),
+            None => notes
+                .iter()
+                .skip(1)
+                .map(|field| strip_html(field))
+                .find(|field| parse_pinyin(field).is_some()),
+        };
+        glyphs.extend(card_glyph(
+            &headword,
+            pinyin.as_deref(),
+            table,
+            &mut unknown,
+        ));
+    }
+
+    ParsedWordList { glyphs, unknown }
+}
+
+/// Text of the first element with a tag, whose opening tag contains `attribute` if given.
+fn element<'a>(xml: &'a str, tag: &str, attribute: Option<&str>) -> Option<&'a str> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+    let mut rest = xml;
+    loop {
+        let start = rest.find(&open)?;
+        let (opening, content) = rest[start..].split_once('>')?;
+        if attribute.is_none_or(|attribute| opening.contains(attribute)) {
+            return content.split_once(close.as_str()).map(|(text, _)| text);
+        }
+        rest = content;
+    }
+}
+
+fn parse_pleco_xml(xml: &str, table: &GlyphTable) -> ParsedWordList {
+    let mut glyphs = vec![];
+    let mut unknown = vec![];
+
+    for card in xml.split("<card").skip(1) {
+        let card = card.split("</card>").next().unwrap_or_default();
+        let headword = element(card, "headword", Some("charset=\"sc\""))
+            .or_else(|| element(card, "headword", None))
+            .map(headword)
+            .unwrap_or_default();
+        let pinyin = element(card, "pron", Some("type=\"hypy\""))
+            .or_else(|| element(card, "pron", None))
+            .map(strip_html);
+        glyphs.extend(card_glyph(
+            &headword,
+            pinyin.as_deref(),
+            table,
+            &mut unknown,
+        ));
+    }
+
+    ParsedWordList { glyphs, unknown }
+}
+
+pub fn parse_deck(text: &str, format: DeckFormat, table: &GlyphTable) -> ParsedWordList {
+    let parsed = match format {
+        DeckFormat::Anki | DeckFormat::PlecoText => parse_text_cards(text, table),
+        DeckFormat::PlecoXml => parse_pleco_xml(text, table),
+    };
+    ParsedWordList {
+        glyphs: parsed
+            .glyphs
+            .into_iter()
+            .unique_by(|glyph| glyph.character.clone())
+            .collect(),
+        unknown: parsed.unknown.into_iter().unique().collect(),
+    }
+}
+
+/// An imported flashcard deck, practised as a [`CharacterSet`].
+pub struct Deck {
+    pub name: String,
+    pub glyphs: Vec<Glyph>,
+}
+
+impl Deck {
+    /// Whether a character is on a card, alone or in a word.
+    pub fn contains(&self, character: char) -> bool {
+        self.glyphs
+            .iter()
+            .any(|glyph| glyph.character.contains(character))
+    }
+}
+
+/// Imported decks, in the order they were first imported.
+#[derive(Default, Resource, Deref, DerefMut)]
+pub struct Decks(Vec<Deck>);
+
+impl Decks {
+    /// Adds a deck, replacing one of the same name.
+    fn insert(&mut self, deck: Deck) {
+        match self.iter_mut().find(|other| other.name == deck.name) {
+            Some(other) => *other = deck,
+            None => self.push(deck),
+        }
+    }
+}
+
+/// Which characters are practised, in a sprint without a word list or as a filter on the word list.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharacterSet {
+    #[default]
+    All,
+    /// The given number of most frequent characters in the [`GlyphTable`].
+    Frequent(usize),
+    /// The cards of an imported deck, by index in [`Decks`].
+    Deck(usize),
+}
+
+impl CharacterSet {
+    /// Sets offered in the settings, the first [`DECKS_SHOWN`] decks included.
+    pub fn options(decks: &Decks) -> Vec<Self> {
+        std::iter::once(Self::All)
+            .chain(FREQUENCY_SETS.into_iter().map(Self::Frequent))
+            .chain((0..decks.len().min(DECKS_SHOWN)).map(Self::Deck))
+            .collect()
+    }
+
+    pub fn label(&self, decks: &Decks) -> String {
+        match self {
+            CharacterSet::All => "all".into(),
+            CharacterSet::Frequent(count) => format!("top {count}"),
+            CharacterSet::Deck(index) => decks
+                .get(*index)
+                .map(|deck| deck.name.chars().take(DECK_LABEL_LENGTH).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Glyphs practised without a word list, each deck card with the pinyin of its card.
+    /// Characters come from the most frequent, so rounds over the same set can be compared.
+    pub fn glyphs(&self, table: &GlyphTable, decks: &Decks) -> Vec<Glyph> {
+        match self {
+            CharacterSet::Frequent(count) => table.most_frequent(*count).cloned().collect(),
+            CharacterSet::Deck(index) if *index < decks.len() => decks[*index].glyphs.clone(),
+            _ => table.most_frequent(usize::MAX).cloned().collect(),
+        }
+    }
+
+    /// Keeps the glyphs whose characters are all in the set.
+    pub fn filter(&self, glyphs: &[Glyph], table: &GlyphTable, decks: &Decks) -> Vec<Glyph> {
+        let contains = |character: char| match self {
+            CharacterSet::All => true,
+            CharacterSet::Frequent(count) => table
+                .rank(&character.to_string())
+                .is_some_and(|rank| rank < *count),
+            CharacterSet::Deck(index) => decks
+                .get(*index)
+                .is_none_or(|deck| deck.contains(character)),
+        };
+        glyphs
+            .iter()
+            .filter(|glyph| glyph.character.chars().all(contains))
+            .cloned()
+            .collect()
+    }
+}
+
+fn write_deck(deck: &Deck) -> io::Result<()> {
+    let dir = data_dir()
+        .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?
+        .join(DECKS_DIR);
+    fs::create_dir_all(&dir)?;
+    fs::write(
+        dir.join(format!("{}.tsv", deck.name)),
+        word_list_tsv(&deck.glyphs),
+    )
+}
+
+/// Restores the decks imported in earlier sessions, saved as word lists.
+pub fn load_decks(table: Res<GlyphTable>, mut decks: ResMut<Decks>) {
+    let entries = match data_dir().map(|dir| fs::read_dir(dir.join(DECKS_DIR))) {
+        Some(Ok(entries)) => entries,
+        _ => return,
+    };
+    for path in entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .sorted()
+    {
+        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+        if let Ok(text) = fs::read_to_string(&path) {
+            decks.insert(Deck {
+                name: name.into(),
+                glyphs: parse_word_list(&text, WordListFormat::Tsv, &table).glyphs,
+            });
+        }
+    }
+}
+
+/// Imports an Anki or Pleco export dropped onto the window as a deck named after the file.
+pub fn import_deck(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    table: Res<GlyphTable>,
+    mut decks: ResMut<Decks>,
+    mut events: EventReader<DroppedFile>,
+    panels: Query<Entity, With<ImportPanel>>,
+) {
+    for event in events.iter() {
+        let (format, text) = match &event.read {
+            Ok((ImportFormat::Deck(format), text)) => (*format, text),
+            _ => continue,
+        };
+        let name = event
+            .path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        let parsed = parse_deck(text, format, &table);
+        let (summary, unknown) = if parsed.glyphs.is_empty() {
+            (
+                format!("No cards with known characters in {name}"),
+                parsed.unknown,
+            )
+        } else {
+            let deck = Deck {
+                name: name.clone(),
+                glyphs: parsed.glyphs,
+            };
+            let summary = format!(
+                "Imported {} cards into the deck {name}\nPick it as the set in Settings",
+                deck.glyphs.len()
+            );
+            if let Err(error) = write_deck(&deck) {
+                warn!("Failed to save the deck: {error}");
+            }
+            decks.insert(deck);
+            (summary, parsed.unknown)
+        };
+
+        let unknown = match unknown.is_empty() {
+            true => String::new(),
+            false => format!(
+                "\nNo pinyin: {}",
+                unknown.iter().take(IMPORT_PANEL_MAX_UNKNOWN).join(" ")
+            ),
+        };
+        spawn_import_panel(&mut commands, &asset_server, &panels, summary, unknown);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(parsed: &ParsedWordList) -> Vec<(&str, &str, &str)> {
+        parsed
+            .glyphs
+            .iter()
+            .map(|glyph| {
+                let code = glyph.codes.first().map_or("", String::as_str);
+                (glyph.character.as_str(), code, glyph.reading.as_str())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn formats_are_told_by_extension_and_contents() {
+        let detect = |name: &str, text: &str| DeckFormat::detect(Path::new(name), text);
+        assert!(detect("cards.xml", "").is_some_and(|f| f == DeckFormat::PlecoXml));
+        assert!(
+            detect("cards.txt", "#separator:tab\n#html:true\n中\tzhōng\n")
+                .is_some_and(|f| f == DeckFormat::Anki)
+        );
+        assert!(detect("cards.txt", "// HSK 1\n中\tzhong1\tmiddle\n")
+            .is_some_and(|f| f == DeckFormat::PlecoText));
+        assert!(detect("words.txt", "中国 你好\n").is_none());
+        assert!(detect("words.txt", "// only a header\n").is_none());
+        assert!(detect("words.tsv", "中\tzhong\n").is_none());
+    }
+
+    #[test]
+    fn pinyin_fields_need_whole_syllables_with_tones() {
+        assert_eq!(
+            parse_pinyin("zhōng"),
+            Some(("zhong".into(), "zhong1".into()))
+        );
+        assert_eq!(parse_pinyin("lu:4"), Some(("lv".into(), "lv4".into())));
+        assert_eq!(
+            parse_pinyin("Zhōngguó"),
+            Some(("zhongguo".into(), "".into()))
+        );
+        assert_eq!(parse_pinyin("xi1 an1"), Some(("xian".into(), "".into())));
+        assert_eq!(parse_pinyin("China"), None);
+        assert_eq!(parse_pinyin("a 3-day trip"), None);
+        assert_eq!(parse_pinyin("café"), None);
+    }
+
+    #[test]
+    fn anki_headers_pick_the_separator_and_columns() {
+        let table = GlyphTable::default();
+        let text = "#separator:Semicolon\n#html:true\n#notetype column:1\n\
+                    #columns:Type;Hanzi;Meaning;Pinyin\n\
+                    Basic;<b>中</b>;middle;zhōng\n\
+                    Basic;好;\"good; hǎo\";hǎo\n";
+        let parsed = parse_deck(text, DeckFormat::Anki, &table);
+        assert_eq!(
+            cards(&parsed),
+            [("中", "zhong", "zhong1"), ("好", "hao", "hao3")]
+        );
+
+        let text = "#separator:tab\n#deck column:2\n中\tHSK\tzhōng\tmiddle\n";
+        let parsed = parse_deck(text, DeckFormat::Anki, &table);
+        assert_eq!(cards(&parsed), [("中", "zhong", "zhong1")]);
+    }
+
+    #[test]
+    fn pleco_xml_cards_use_the_simplified_headword() {
+        let table = GlyphTable::default();
+        let xml = r#"<plecoflash><cards>
+            <card><entry><headword charset="tc">國</headword><headword charset="sc">国</headword>
+            <pron type="hypy" tones="numbers">guo2</pron><defn>country</defn></entry></card>
+            <card><entry><headword charset="sc">中国[中國]</headword>
+            <pron type="hypy">zhong1 guo2</pron></entry></card>
+            <card><entry><headword charset="sc">𪚥</headword></entry></card>
+        </cards></plecoflash>"#;
+        let parsed = parse_deck(xml, DeckFormat::PlecoXml, &table);
+        assert_eq!(
+            cards(&parsed),
+            [("国", "guo", "guo2"), ("中国", "zhongguo", "")]
+        );
+        assert_eq!(parsed.unknown, ["𪚥"]);
+    }
+
+    #[test]
+    fn syllables_are_counted_longest_first() {
+        assert_eq!(count_syllables("zhongguo"), Some(2));
+        assert_eq!(count_syllables("xian"), Some(1));
+        assert_eq!(count_syllables("xiaoxuesheng"), Some(3));
+        assert_eq!(count_syllables(""), Some(0));
+        assert_eq!(count_syllables("trip"), None);
+    }
+
+    #[test]
+    fn long_letters_are_counted_without_backtracking() {
+        assert_eq!(count_syllables(&"a".repeat(200)), Some(200));
+        assert_eq!(count_syllables(&format!("{}q", "a".repeat(200))), None);
+    }
+}
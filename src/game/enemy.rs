@@ -1,5 +1,6 @@
 use super::{
     ball::{Ball, Trajectory},
+    base::PlayerBase,
     physics::Motion,
 };
 use crate::{constants::*, utils::Damp, TimeScale};
@@ -56,6 +57,7 @@ pub fn control_enemy(
     time: Res<Time>,
     mut query: Query<(&Transform, &Enemy, &mut Controller), Without<Ball>>,
     ball_query: Query<(&Transform, &Motion, &Trajectory), With<Ball>>,
+    bases: Query<(), With<PlayerBase>>,
 ) {
     for (transform, enemy, mut controller) in query.iter_mut() {
         controller.velocity = Vec2::ZERO;
@@ -77,7 +79,7 @@ pub fn control_enemy(
                 let delta_seconds = time.elapsed_seconds() - trajectory.start_time;
 
                 if let Some(candidate) = trajectory
-                    .points
+                    .in_play(&bases)
                     .iter()
                     .filter(|point| point.position.y > 0.0)
                     .filter(|point| point.position.y < ARENA_HEIGHT * 0.375)
@@ -113,7 +115,7 @@ pub fn control_enemy(
                 } else {
                     // not found, choose the average trajectory points as the candidate.
                     let collection: Vec<_> = trajectory
-                        .points
+                        .in_play(&bases)
                         .iter()
                         .map(|point| point.position)
                         .filter(|position| position.y > -ARENA_HEIGHT / 2.0)
@@ -0,0 +1,84 @@
+use super::{
+    ball::Ball,
+    physics::{Motion, RigidBody},
+};
+use crate::{constants::PHYSICS_TIME_STEP, utils::Shape, TimeScale};
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Force {
+    /// Constant force, pushing lighter bodies harder.
+    Wind(Vec2),
+    /// Force towards the center of the field, or away from it if negative.
+    Radial(f32),
+    /// Scales the gravity of balls inside the field.
+    GravityScale(f32),
+}
+
+/// An area, centered on the transform, that pushes bodies with [`Motion`] inside it every physics step.
+#[derive(Debug, Clone, Component)]
+pub struct ForceField {
+    pub force: Force,
+    pub area: Shape,
+}
+
+impl ForceField {
+    pub fn new(force: Force, area: Shape) -> Self {
+        Self { force, area }
+    }
+
+    /// Acceleration from the field centered at `center` of a body at `position`,
+    /// with `gravity` the body's own, outside any field.
+    pub fn acceleration(
+        &self,
+        center: Vec2,
+        position: Vec2,
+        inverted_mass: f32,
+        gravity: Vec2,
+    ) -> Vec2 {
+        let offset = position - center;
+        if !self.area.contains(offset) {
+            return Vec2::ZERO;
+        }
+
+        match self.force {
+            Force::Wind(force) => force * inverted_mass,
+            Force::Radial(force) => -force * offset.normalize_or_zero() * inverted_mass,
+            Force::GravityScale(scale) => (scale - 1.0) * gravity,
+        }
+    }
+}
+
+/// Sum of the accelerations of all fields on a body.
+pub fn field_acceleration<'a>(
+    fields: impl IntoIterator<Item = (&'a ForceField, &'a Transform)>,
+    position: Vec2,
+    inverted_mass: f32,
+    gravity: Vec2,
+) -> Vec2 {
+    fields
+        .into_iter()
+        .map(|(field, transform)| {
+            let center = transform.translation.truncate();
+            field.acceleration(center, position, inverted_mass, gravity)
+        })
+        .sum()
+}
+
+pub fn apply_force_fields(
+    time_scale: Res<TimeScale>,
+    fields: Query<(&ForceField, &Transform)>,
+    mut query: Query<(&RigidBody, &mut Motion, Option<&Ball>)>,
+) {
+    if fields.is_empty() {
+        return;
+    }
+
+    let delta_time = PHYSICS_TIME_STEP * time_scale.0;
+    for (rigid_body, mut motion, ball) in query.iter_mut() {
+        let gravity = Vec2::new(0.0, ball.map_or(0.0, |ball| ball.gravity));
+        let position = motion.translation.truncate();
+        let acceleration = field_acceleration(&fields, position, rigid_body.inverted_mass, gravity);
+        motion.velocity += acceleration * delta_time;
+    }
+}
@@ -0,0 +1,125 @@
+use super::{
+    glyph::Glyph,
+    keyboard::next_key,
+    layout::{Finger, KeyLayout, TypedEvent},
+    physics::Motion,
+    player::InputCodes,
+    scheme::ActiveScheme,
+};
+use bevy::prelude::*;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// Whether the paddle shows which finger presses the next key.
+#[derive(Default, Resource)]
+pub struct FingerGuide {
+    pub enabled: bool,
+}
+
+/// Finger usage during the current round.
+#[derive(Default, Resource)]
+pub struct FingerStats {
+    pub load: HashMap<Finger, u32>,
+    /// Consecutive keys within a code typed by the same finger on different keys.
+    pub same_finger_bigrams: HashMap<(char, char), u32>,
+    /// The previous correct key within the code being typed.
+    previous: Option<char>,
+}
+
+impl FingerStats {
+    /// Share of keystrokes typed by a finger, between 0 and 1.
+    pub fn share(&self, finger: Finger) -> f32 {
+        let total = self.load.values().sum::<u32>();
+        match total {
+            0 => 0.0,
+            total => self.load.get(&finger).copied().unwrap_or_default() as f32 / total as f32,
+        }
+    }
+
+    /// Same-finger bigrams, most frequent first.
+    pub fn frequent_bigrams(&self) -> impl Iterator<Item = (&(char, char), &u32)> {
+        self.same_finger_bigrams
+            .iter()
+            .sorted_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)))
+    }
+}
+
+/// Counts the fingers of correct keystrokes against the code of the glyph in play.
+/// Runs before [`handle_input`](super::player::handle_input) so the codes typed so far are those before each key.
+pub fn record_fingers(
+    layout: Res<KeyLayout>,
+    scheme: ActiveScheme,
+    mut events: EventReader<TypedEvent>,
+    mut stats: ResMut<FingerStats>,
+    glyph_query: Query<&Glyph, With<Motion>>,
+    input_query: Query<&Text, With<InputCodes>>,
+) {
+    let mut typed = match input_query.get_single() {
+        Ok(text) => text.sections[0].value.clone(),
+        Err(_) => return,
+    };
+    let code = glyph_query
+        .iter()
+        .next()
+        .and_then(|glyph| scheme.first_code(glyph))
+        .unwrap_or_default();
+
+    for event in events.iter() {
+        let key = event.char;
+        let correct = next_key(&code, &typed) == Some(key);
+        match key {
+            ' ' => typed.clear(),
+            key => typed.push(key),
+        }
+
+        // a mistake breaks the run of keys
+        if !correct {
+            stats.previous = None;
+            continue;
+        }
+
+        let finger = layout.finger(key);
+        if let Some(finger) = finger {
+            *stats.load.entry(finger).or_default() += 1;
+        }
+
+        if let Some(previous) = stats.previous.filter(|previous| {
+            *previous != key && finger.is_some() && layout.finger(*previous) == finger
+        }) {
+            *stats
+                .same_finger_bigrams
+                .entry((previous, key))
+                .or_default() += 1;
+        }
+
+        stats.previous = (key != ' ').then_some(key);
+    }
+}
+
+#[derive(Component)]
+pub struct FingerHint;
+
+/// Shows the finger for the next expected key under the paddle.
+pub fn finger_hint(
+    guide: Res<FingerGuide>,
+    scheme: ActiveScheme,
+    layout: Res<KeyLayout>,
+    glyph_query: Query<&Glyph, With<Motion>>,
+    input_query: Query<&Text, (With<InputCodes>, Without<FingerHint>)>,
+    mut query: Query<&mut Text, With<FingerHint>>,
+) {
+    let finger = match (glyph_query.iter().next(), input_query.get_single()) {
+        (Some(glyph), Ok(text)) if guide.enabled => scheme
+            .first_code(glyph)
+            .and_then(|code| next_key(&code, &text.sections[0].value))
+            .and_then(|key| layout.finger(key)),
+        _ => None,
+    };
+
+    for mut text in query.iter_mut() {
+        text.sections[0].value = finger
+            .map(|finger| finger.label())
+            .unwrap_or_default()
+            .into();
+    }
+}
@@ -0,0 +1,256 @@
+use super::{glyph::AnswerEvent, key_stats::KeyStats, scheme::ActiveScheme};
+use crate::constants::FUZZY_WORD_VARIANTS;
+use bevy::prelude::*;
+use itertools::Itertools;
+use std::collections::HashSet;
+
+/// A pair of sounds accepted in place of each other, as offered by common pinyin IMEs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FuzzyRule {
+    ZZh,
+    CCh,
+    SSh,
+    NL,
+    AnAng,
+    EnEng,
+    InIng,
+}
+
+impl FuzzyRule {
+    pub const ALL: [FuzzyRule; 7] = [
+        FuzzyRule::ZZh,
+        FuzzyRule::CCh,
+        FuzzyRule::SSh,
+        FuzzyRule::NL,
+        FuzzyRule::AnAng,
+        FuzzyRule::EnEng,
+        FuzzyRule::InIng,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FuzzyRule::ZZh => "z/zh",
+            FuzzyRule::CCh => "c/ch",
+            FuzzyRule::SSh => "s/sh",
+            FuzzyRule::NL => "n/l",
+            FuzzyRule::AnAng => "an/ang",
+            FuzzyRule::EnEng => "en/eng",
+            FuzzyRule::InIng => "in/ing",
+        }
+    }
+
+    /// The two spellings, the longer one first so it's matched before its prefix or suffix.
+    fn pair(&self) -> (&'static str, &'static str) {
+        match self {
+            FuzzyRule::ZZh => ("zh", "z"),
+            FuzzyRule::CCh => ("ch", "c"),
+            FuzzyRule::SSh => ("sh", "s"),
+            FuzzyRule::NL => ("n", "l"),
+            FuzzyRule::AnAng => ("ang", "an"),
+            FuzzyRule::EnEng => ("eng", "en"),
+            FuzzyRule::InIng => ("ing", "in"),
+        }
+    }
+
+    fn is_initial(&self) -> bool {
+        matches!(
+            self,
+            FuzzyRule::ZZh | FuzzyRule::CCh | FuzzyRule::SSh | FuzzyRule::NL
+        )
+    }
+
+    /// The syllable with the other spelling of the pair, if it has either.
+    fn apply(&self, syllable: &str) -> Option<String> {
+        let (long, short) = self.pair();
+        if self.is_initial() {
+            [(long, short), (short, long)]
+                .into_iter()
+                .find_map(|(from, to)| Some(format!("{to}{}", syllable.strip_prefix(from)?)))
+        } else {
+            [(long, short), (short, long)]
+                .into_iter()
+                .find_map(|(from, to)| Some(format!("{}{to}", syllable.strip_suffix(from)?)))
+        }
+    }
+}
+
+/// Fuzzy rules applied when checking pinyin answers.
+#[derive(Default, Resource)]
+pub struct FuzzyPinyin {
+    pub rules: HashSet<FuzzyRule>,
+}
+
+impl FuzzyPinyin {
+    /// Spellings of a toneless pinyin syllable under the enabled rules, with the rules each one needs.
+    /// The syllable itself isn't included; at most one initial and one final rule apply.
+    pub fn variants(&self, syllable: &str) -> Vec<(String, Vec<FuzzyRule>)> {
+        let enabled = |initial: bool| {
+            FuzzyRule::ALL
+                .into_iter()
+                .filter(move |rule| rule.is_initial() == initial && self.rules.contains(rule))
+        };
+        let initials = enabled(true)
+            .filter_map(|rule| Some((rule.apply(syllable)?, vec![rule])))
+            .chain(std::iter::once((syllable.to_string(), vec![])));
+
+        let mut variants = vec![];
+        for (spelling, rules) in initials {
+            for rule in enabled(false) {
+                if let Some(spelling) = rule.apply(&spelling) {
+                    let rules = rules.iter().copied().chain([rule]).collect();
+                    variants.push((spelling, rules));
+                }
+            }
+            if !rules.is_empty() {
+                variants.push((spelling, rules));
+            }
+        }
+        variants
+    }
+
+    /// Spellings of a word, a syllable at a time, with the rules fuzzily spelled syllables need.
+    /// The word itself isn't included, and there are at most [`FUZZY_WORD_VARIANTS`] of them.
+    pub fn word_variants(&self, syllables: &[&str]) -> Vec<(Vec<String>, Vec<FuzzyRule>)> {
+        let mut words = vec![(vec![], vec![])];
+        for syllable in syllables {
+            let spellings = std::iter::once((syllable.to_string(), vec![]))
+                .chain(self.variants(syllable))
+                .collect_vec();
+            words = words
+                .iter()
+                .cartesian_product(&spellings)
+                .map(|((word, rules), (spelling, more))| {
+                    let word = word.iter().chain([spelling]).cloned().collect_vec();
+                    let rules = rules.iter().chain(more).copied().sorted().dedup().collect();
+                    (word, rules)
+                })
+                // the word spelled as it is comes first, and is dropped below
+                .take(FUZZY_WORD_VARIANTS + 1)
+                .collect();
+        }
+        words.retain(|(_, rules)| !rules.is_empty());
+        words
+    }
+}
+
+/// Counts the fuzzy rules correct answers needed, in the statistics of the active scheme.
+pub fn record_fuzzy(
+    scheme: ActiveScheme,
+    mut answer_events: EventReader<AnswerEvent>,
+    mut stats: ResMut<KeyStats>,
+) {
+    for event in answer_events.iter() {
+        if !event.correct || event.fuzzy.is_empty() {
+            continue;
+        }
+        let stats = stats.entry(scheme.name().into()).or_default();
+        for rule in &event.fuzzy {
+            *stats.fuzzy.entry(*rule).or_default() += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fuzzy(rules: &[FuzzyRule]) -> FuzzyPinyin {
+        FuzzyPinyin {
+            rules: rules.iter().copied().collect(),
+        }
+    }
+
+    fn spellings(variants: Vec<(String, Vec<FuzzyRule>)>) -> Vec<String> {
+        variants
+            .into_iter()
+            .map(|(spelling, _)| spelling)
+            .sorted()
+            .collect()
+    }
+
+    #[test]
+    fn final_rules_swap_either_way() {
+        let fuzzy = fuzzy(&[FuzzyRule::AnAng, FuzzyRule::EnEng, FuzzyRule::InIng]);
+        assert_eq!(
+            fuzzy.variants("fan"),
+            [("fang".into(), vec![FuzzyRule::AnAng])]
+        );
+        assert_eq!(
+            fuzzy.variants("fang"),
+            [("fan".into(), vec![FuzzyRule::AnAng])]
+        );
+        assert_eq!(
+            fuzzy.variants("gen"),
+            [("geng".into(), vec![FuzzyRule::EnEng])]
+        );
+        assert_eq!(
+            fuzzy.variants("geng"),
+            [("gen".into(), vec![FuzzyRule::EnEng])]
+        );
+        assert_eq!(
+            fuzzy.variants("xin"),
+            [("xing".into(), vec![FuzzyRule::InIng])]
+        );
+        assert_eq!(
+            fuzzy.variants("xing"),
+            [("xin".into(), vec![FuzzyRule::InIng])]
+        );
+        assert!(fuzzy.variants("ma").is_empty());
+    }
+
+    #[test]
+    fn initial_and_final_rules_combine() {
+        let fuzzy = fuzzy(&[FuzzyRule::ZZh, FuzzyRule::EnEng]);
+        assert_eq!(spellings(fuzzy.variants("zhen")), ["zen", "zeng", "zheng"]);
+        assert_eq!(
+            fuzzy.variants("zen"),
+            [
+                ("zheng".into(), vec![FuzzyRule::ZZh, FuzzyRule::EnEng]),
+                ("zhen".into(), vec![FuzzyRule::ZZh]),
+                ("zeng".into(), vec![FuzzyRule::EnEng]),
+            ]
+        );
+    }
+
+    #[test]
+    fn word_rules_apply_to_each_syllable() {
+        let fuzzy = fuzzy(&[FuzzyRule::SSh, FuzzyRule::InIng]);
+        let variants = fuzzy.word_variants(&["shen", "xin"]);
+        let words = variants
+            .iter()
+            .map(|(word, _)| word.concat())
+            .sorted()
+            .collect_vec();
+        assert_eq!(words, ["senxin", "senxing", "shenxing"]);
+
+        let rules = |word: &str| {
+            variants
+                .iter()
+                .find(|(spellings, _)| spellings.concat() == word)
+                .map(|(_, rules)| rules.clone())
+        };
+        assert_eq!(rules("shenxing"), Some(vec![FuzzyRule::InIng]));
+        assert_eq!(
+            rules("senxing"),
+            Some(vec![FuzzyRule::SSh, FuzzyRule::InIng])
+        );
+    }
+
+    #[test]
+    fn word_rules_are_counted_once() {
+        let fuzzy = fuzzy(&[FuzzyRule::AnAng]);
+        let variants = fuzzy.word_variants(&["fan", "fan"]);
+        assert_eq!(variants.len(), 3);
+        assert!(variants
+            .iter()
+            .all(|(_, rules)| *rules == [FuzzyRule::AnAng]));
+    }
+
+    #[test]
+    fn word_variants_are_capped() {
+        let fuzzy = fuzzy(&FuzzyRule::ALL);
+        let variants = fuzzy.word_variants(&["zhang", "chen", "shin", "nan"]);
+        assert_eq!(variants.len(), FUZZY_WORD_VARIANTS);
+        assert!(variants.iter().all(|(_, rules)| !rules.is_empty()));
+    }
+}
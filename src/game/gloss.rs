@@ -0,0 +1,293 @@
+use super::{
+    glyph::{AnswerEvent, Glyph},
+    keyboard::KeyboardOverlay,
+    Cleanup,
+};
+use crate::{constants::*, utils::data_dir};
+use bevy::prelude::*;
+use itertools::Itertools;
+use std::{collections::HashMap, fs};
+
+/// Whether a gloss is shown after a character is answered.
+#[derive(Resource)]
+pub struct GlossDisplay {
+    pub enabled: bool,
+}
+
+impl Default for GlossDisplay {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+#[derive(Clone)]
+pub struct Example {
+    pub word: String,
+    pub reading: String,
+    pub definition: String,
+}
+
+/// Readings and meaning of a single character.
+#[derive(Default, Clone)]
+pub struct Gloss {
+    pub readings: Vec<String>,
+    pub definitions: Vec<String>,
+    pub example: Option<Example>,
+}
+
+/// Glosses by simplified character, parsed from a CC-CEDICT file.
+/// Empty when there is no dictionary in the data directory.
+#[derive(Default, Resource, Deref)]
+pub struct Dictionary(HashMap<String, Gloss>);
+
+/// A CC-CEDICT line: traditional, simplified, reading and definitions,
+/// e.g. `傳統 传统 [chuan2 tong3] /tradition/traditional/`.
+fn parse_line(line: &str) -> Option<(&str, &str, Vec<&str>)> {
+    let (_traditional, rest) = line.split_once(' ')?;
+    let (simplified, rest) = rest.split_once(' ')?;
+    let (reading, rest) = rest.strip_prefix('[')?.split_once(']')?;
+    let definitions = rest
+        .trim()
+        .trim_matches('/')
+        .split('/')
+        .filter(|definition| !definition.is_empty())
+        .collect();
+    Some((simplified, reading, definitions))
+}
+
+/// Definitions that only point elsewhere, skipped when a character has better ones.
+fn is_reference(definition: &str) -> bool {
+    ["surname ", "variant of ", "old variant of ", "used in "]
+        .iter()
+        .any(|prefix| definition.starts_with(prefix))
+}
+
+pub fn parse_dictionary(text: &str) -> Dictionary {
+    let mut glosses = HashMap::<String, Gloss>::new();
+    let mut examples = HashMap::<char, Example>::new();
+
+    for (simplified, reading, definitions) in text
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(parse_line)
+    {
+        match simplified.chars().count() {
+            1 => {
+                let gloss = glosses.entry(simplified.into()).or_default();
+                gloss.readings.push(reading.into());
+                gloss
+                    .definitions
+                    .extend(definitions.into_iter().map(String::from));
+            }
+            // the first two-character word is the example of both its characters
+            2 => {
+                for character in simplified.chars() {
+                    examples.entry(character).or_insert_with(|| Example {
+                        word: simplified.into(),
+                        reading: reading.into(),
+                        definition: definitions.first().copied().unwrap_or_default().into(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (character, gloss) in glosses.iter_mut() {
+        if gloss.definitions.iter().any(|d| !is_reference(d)) {
+            gloss.definitions.retain(|d| !is_reference(d));
+        }
+        gloss.readings = gloss.readings.drain(..).unique().collect();
+        gloss.example = character
+            .chars()
+            .next()
+            .and_then(|c| examples.get(&c).cloned());
+    }
+
+    Dictionary(glosses)
+}
+
+/// Parses the dictionary once at startup, so answering only does a map lookup.
+pub fn load_dictionary(mut dictionary: ResMut<Dictionary>) {
+    if let Some(path) = data_dir().map(|dir| dir.join(DICTIONARY_FILE)) {
+        if let Ok(text) = fs::read_to_string(path) {
+            *dictionary = parse_dictionary(&text);
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct GlossPanel(Timer);
+
+/// Shows the gloss of a correctly answered glyph under the arena, replacing the previous one.
+/// Runs before [`advance_glyph`](super::glyph::advance_glyph) replaces the glyph.
+#[allow(clippy::too_many_arguments)]
+pub fn show_gloss(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    display: Res<GlossDisplay>,
+    overlay: Res<KeyboardOverlay>,
+    dictionary: Res<Dictionary>,
+    mut answer_events: EventReader<AnswerEvent>,
+    glyph_query: Query<&Glyph>,
+    panels: Query<Entity, With<GlossPanel>>,
+) {
+    for event in answer_events.iter() {
+        if !display.enabled || !event.correct {
+            continue;
+        }
+        let glyph = match glyph_query.get(event.ball) {
+            Ok(glyph) => glyph,
+            Err(_) => continue,
+        };
+        let gloss = match dictionary.get(&glyph.character) {
+            Some(gloss) => gloss,
+            None => continue,
+        };
+
+        for entity in panels.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        let style = TextStyle {
+            font: asset_server.load(FONT_LXGW),
+            font_size: 16.0,
+            color: Color::WHITE,
+        };
+        let definitions = gloss
+            .definitions
+            .iter()
+            .take(GLOSS_MAX_DEFINITIONS)
+            .join("; ");
+        let example = gloss
+            .example
+            .as_ref()
+            .map(|example| {
+                format!(
+                    "\n{} [{}] {}",
+                    example.word, example.reading, example.definition
+                )
+            })
+            .unwrap_or_default();
+
+        // keep clear of the on-screen keyboard
+        let bottom = if overlay.enabled {
+            GLOSS_KEYBOARD_OFFSET
+        } else {
+            8.0
+        };
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Auto),
+                        position_type: PositionType::Absolute,
+                        position: UiRect {
+                            bottom: Val::Px(bottom),
+                            ..Default::default()
+                        },
+                        justify_content: JustifyContent::Center,
+                        ..Default::default()
+                    },
+                    background_color: Color::NONE.into(),
+                    ..Default::default()
+                },
+                GlossPanel(Timer::from_seconds(GLOSS_DURATION, TimerMode::Once)),
+                Cleanup,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            max_size: Size::new(Val::Percent(90.0), Val::Auto),
+                            padding: UiRect::all(Val::Px(8.0)),
+                            ..Default::default()
+                        },
+                        background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_sections([
+                            TextSection::new(
+                                format!("{} ", glyph.character),
+                                TextStyle {
+                                    color: Color::GOLD,
+                                    ..style.clone()
+                                },
+                            ),
+                            TextSection::new(
+                                format!("[{}] {definitions}", gloss.readings.join(", ")),
+                                style.clone(),
+                            ),
+                            TextSection::new(
+                                example,
+                                TextStyle {
+                                    color: Color::GRAY,
+                                    ..style
+                                },
+                            ),
+                        ]));
+                    });
+            });
+    }
+}
+
+pub fn gloss_panel(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut GlossPanel)>,
+) {
+    for (entity, mut panel) in query.iter_mut() {
+        if panel.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_give_simplified_reading_and_definitions() {
+        assert_eq!(
+            parse_line("傳統 传统 [chuan2 tong3] /tradition/traditional/"),
+            Some(("传统", "chuan2 tong3", vec!["tradition", "traditional"]))
+        );
+        assert_eq!(
+            parse_line("中 中 [zhong1] /China/middle; center/"),
+            Some(("中", "zhong1", vec!["China", "middle; center"]))
+        );
+        assert_eq!(parse_line("中 中 [zhong1]"), Some(("中", "zhong1", vec![])));
+        assert_eq!(parse_line("中 中 zhong1 /middle/"), None);
+        assert_eq!(parse_line("中"), None);
+    }
+
+    #[test]
+    fn dictionary_merges_readings_and_finds_examples() {
+        let dictionary = parse_dictionary(
+            "# CC-CEDICT\n\
+             行 行 [xing2] /to walk/surname Xing/\n\
+             行 行 [hang2] /row/\n\
+             銀行 银行 [yin2 hang2] /bank/\n\
+             行人 行人 [xing2 ren2] /pedestrian/\n\
+             叚 叚 [jia3] /variant of 假/\n",
+        );
+        let gloss = &dictionary["行"];
+        assert_eq!(gloss.readings, ["xing2", "hang2"]);
+        assert_eq!(gloss.definitions, ["to walk", "row"]);
+        let example = gloss.example.as_ref().unwrap();
+        assert_eq!(
+            (
+                example.word.as_str(),
+                example.reading.as_str(),
+                example.definition.as_str()
+            ),
+            ("银行", "yin2 hang2", "bank")
+        );
+
+        // references are kept when there's nothing else
+        assert_eq!(dictionary["叚"].definitions, ["variant of 假"]);
+        assert!(dictionary["叚"].example.is_none());
+    }
+}
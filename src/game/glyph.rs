@@ -0,0 +1,293 @@
+use super::{
+    fuzzy::{FuzzyPinyin, FuzzyRule},
+    physics::Motion,
+    player::{ConfirmEvent, Player},
+    scheme::ActiveScheme,
+    TypingMode,
+};
+use crate::constants::*;
+use bevy::prelude::*;
+use itertools::Itertools;
+use std::{collections::HashMap, ops::Deref};
+
+/// The character carried by a ball, together with the reading it is pronounced with.
+#[derive(Clone, Component)]
+pub struct Glyph {
+    pub character: String,
+    /// For polyphonic characters this is the reading used in the word the glyph was taken from,
+    /// as found by [`GlyphTable::read`], so dictation doesn't play the reading of another word.
+    pub reading: String,
+    /// Codes accepted as a correct answer, encoded by the active [`Scheme`](super::scheme::Scheme) when they are pinyin.
+    pub codes: Vec<String>,
+}
+
+impl Glyph {
+    pub fn new(character: &str, reading: &str, codes: &[&str]) -> Self {
+        Self {
+            character: character.into(),
+            reading: reading.into(),
+            codes: codes.iter().map(|code| code.to_string()).collect(),
+        }
+    }
+
+    /// Makes a glyph typed with its toneless pinyin, e.g. `zhong` for `zhong1`.
+    pub fn from_reading(character: &str, reading: &str) -> Self {
+        let code = reading.trim_end_matches(|c: char| c.is_ascii_digit());
+        Self::new(character, reading, &[code])
+    }
+
+    /// Toneless syllables of the reading, one for each character of a word.
+    pub fn syllables(&self) -> Vec<&str> {
+        self.reading
+            .split_whitespace()
+            .map(|syllable| syllable.trim_end_matches(|c: char| c.is_ascii_digit()))
+            .collect()
+    }
+
+    /// Paths of the syllable recordings, e.g. `audios/syllables/zhong1.ogg`, in the order they're read.
+    #[cfg(feature = "audio")]
+    pub fn audio_paths(&self) -> impl Iterator<Item = String> + '_ {
+        self.reading
+            .split_whitespace()
+            .map(|syllable| format!("{}/{}.ogg", PRONUNCIATION_AUDIO_PATH, syllable))
+    }
+}
+
+/// Whether a character is a CJK ideograph, rather than a letter, digit or punctuation.
+pub fn is_han(character: char) -> bool {
+    matches!(character,
+        '\u{4e00}'..='\u{9fff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{20000}'..='\u{2ebef}')
+}
+
+/// Known glyphs by character, used to look up codes for imported text.
+#[derive(Resource)]
+pub struct GlyphTable {
+    glyphs: HashMap<String, Glyph>,
+    /// Characters from the most to the least frequent, in the order of the bundled table.
+    frequency: Vec<String>,
+    /// Readings of the characters of bundled words, for polyphonic characters read otherwise in them.
+    words: HashMap<String, Vec<String>>,
+    /// Characters in the longest bundled word.
+    word_length: usize,
+}
+
+impl Default for GlyphTable {
+    /// Loads the bundled table of common characters.
+    fn default() -> Self {
+        let frequency: Vec<Glyph> = include_str!("glyphs.tsv")
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(character, reading)| Glyph::from_reading(character, reading))
+            .collect();
+        let words: HashMap<String, Vec<String>> = include_str!("words.tsv")
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(word, readings)| {
+                let readings = readings.split_whitespace().map(String::from).collect();
+                (word.to_string(), readings)
+            })
+            .filter(|(word, readings): &(String, Vec<_>)| word.chars().count() == readings.len())
+            .collect();
+        Self {
+            glyphs: frequency
+                .iter()
+                .map(|glyph| (glyph.character.clone(), glyph.clone()))
+                .collect(),
+            frequency: frequency.into_iter().map(|glyph| glyph.character).collect(),
+            word_length: words.values().map(Vec::len).max().unwrap_or_default(),
+            words,
+        }
+    }
+}
+
+impl Deref for GlyphTable {
+    type Target = HashMap<String, Glyph>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.glyphs
+    }
+}
+
+impl GlyphTable {
+    /// Position of a character in the frequency order, 0 for the most frequent.
+    pub fn rank(&self, character: &str) -> Option<usize> {
+        self.frequency.iter().position(|other| other == character)
+    }
+
+    /// Glyphs of the characters of a text, each with its reading in the longest bundled word
+    /// starting there, else its most common one; `Err` for characters missing from the table.
+    pub fn read(&self, text: &str) -> Vec<Result<Glyph, char>> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut glyphs = Vec::with_capacity(chars.len());
+        let mut index = 0;
+        while index < chars.len() {
+            let longest = self.word_length.min(chars.len() - index);
+            let word = (2..=longest).rev().find_map(|length| {
+                let word: String = chars[index..index + length].iter().collect();
+                self.words.get(&word)
+            });
+
+            match word {
+                Some(readings) => {
+                    for (character, reading) in chars[index..].iter().zip(readings) {
+                        glyphs.push(Ok(Glyph::from_reading(&character.to_string(), reading)));
+                    }
+                    index += readings.len();
+                }
+                None => {
+                    let character = chars[index];
+                    glyphs.push(self.get(&character.to_string()).cloned().ok_or(character));
+                    index += 1;
+                }
+            }
+        }
+        glyphs
+    }
+
+    /// Glyph of a word, typed with the codes of its characters one after another
+    /// and read with their readings in the word; `Err` with the characters missing from the table.
+    pub fn word(&self, text: &str) -> Result<Glyph, Vec<char>> {
+        let (glyphs, missing): (Vec<_>, Vec<_>) = self.read(text).into_iter().partition_result();
+        if !missing.is_empty() || glyphs.is_empty() {
+            return Err(missing);
+        }
+        if let [glyph] = glyphs.as_slice() {
+            return Ok(glyph.clone());
+        }
+
+        let reading = glyphs.iter().map(|glyph| glyph.reading.as_str()).join(" ");
+        let code: String = glyphs
+            .iter()
+            .filter_map(|glyph| glyph.codes.first())
+            .map(String::as_str)
+            .collect();
+        Ok(Glyph::new(text, &reading, &[&code]))
+    }
+
+    pub fn most_frequent(&self, count: usize) -> impl Iterator<Item = &Glyph> {
+        self.frequency
+            .iter()
+            .take(count)
+            .filter_map(|character| self.glyphs.get(character))
+    }
+}
+
+/// Glyphs the ball of a Practice round takes in turn, filled as the round starts
+/// from the word list, a character set or the reviewed mistakes.
+/// When empty the ball keeps its default glyph.
+#[derive(Default, Resource)]
+pub struct GlyphQueue {
+    pub glyphs: Vec<Glyph>,
+    pub index: usize,
+}
+
+#[cfg(feature = "audio")]
+pub struct PronounceEvent {
+    pub ball: Entity,
+}
+
+pub struct AnswerEvent {
+    pub ball: Entity,
+    pub codes: String,
+    pub correct: bool,
+    /// Fuzzy rules the codes needed to be correct, empty for an exact answer.
+    pub fuzzy: Vec<FuzzyRule>,
+}
+
+/// Checks confirmed codes against the glyphs in play, allowing the enabled fuzzy pinyin rules.
+/// Sends one [`AnswerEvent`] for the ball the codes answer, exactly rather than fuzzily,
+/// or for the ball nearest to the player if they answer none.
+pub fn check_answer(
+    scheme: ActiveScheme,
+    fuzzy: Res<FuzzyPinyin>,
+    mut confirm_events: EventReader<ConfirmEvent>,
+    mut answer_events: EventWriter<AnswerEvent>,
+    players: Query<&Motion, With<Player>>,
+    query: Query<(Entity, &Glyph, &Motion)>,
+) {
+    let player = players
+        .get_single()
+        .map_or(Vec3::ZERO, |motion| motion.translation);
+
+    for event in confirm_events.iter() {
+        if event.codes.is_empty() {
+            continue;
+        }
+
+        let answers = query.iter().map(|(ball, glyph, motion)| {
+            let fuzzy = match scheme.codes(glyph).contains(&event.codes) {
+                true => Some(vec![]),
+                false => scheme.fuzzy_rules(glyph, &event.codes, &fuzzy),
+            };
+            let distance = motion.translation.distance_squared(player);
+            (ball, fuzzy, distance)
+        });
+        let rank = |fuzzy: &Option<Vec<FuzzyRule>>| match fuzzy {
+            Some(rules) => usize::from(!rules.is_empty()),
+            None => 2,
+        };
+        let target = answers.min_by(|(_, fuzzy, distance), (_, other_fuzzy, other_distance)| {
+            rank(fuzzy)
+                .cmp(&rank(other_fuzzy))
+                .then(distance.total_cmp(other_distance))
+        });
+
+        if let Some((ball, fuzzy, _)) = target {
+            answer_events.send(AnswerEvent {
+                ball,
+                codes: event.codes.clone(),
+                correct: fuzzy.is_some(),
+                fuzzy: fuzzy.unwrap_or_default(),
+            });
+        }
+    }
+}
+
+/// Reveals the glyph hidden by dictation once its ball is answered.
+pub fn reveal_glyph(
+    mut answer_events: EventReader<AnswerEvent>,
+    mut query: Query<(&Glyph, &mut Text)>,
+) {
+    for event in answer_events.iter() {
+        if let Ok((glyph, mut text)) = query.get_mut(event.ball) {
+            text.sections[0].value = glyph.character.clone();
+        }
+    }
+}
+
+/// Moves the ball on to the next glyph of the queue after a correct answer.
+pub fn advance_glyph(
+    typing_mode: Res<TypingMode>,
+    mut answer_events: EventReader<AnswerEvent>,
+    #[cfg(feature = "audio")] mut pronounce_events: EventWriter<PronounceEvent>,
+    mut queue: ResMut<GlyphQueue>,
+    mut query: Query<(&mut Glyph, &mut Text)>,
+) {
+    for event in answer_events.iter() {
+        if !event.correct || queue.glyphs.is_empty() {
+            continue;
+        }
+
+        queue.index = (queue.index + 1) % queue.glyphs.len();
+        if let Ok((mut glyph, mut text)) = query.get_mut(event.ball) {
+            *glyph = queue.glyphs[queue.index].clone();
+            text.sections[0].value = match *typing_mode {
+                TypingMode::Normal | TypingMode::Sentence => glyph.character.clone(),
+                TypingMode::Dictation => {
+                    #[cfg(feature = "audio")]
+                    pronounce_events.send(PronounceEvent { ball: event.ball });
+                    DICTATION_PLACEHOLDER.into()
+                }
+            };
+        }
+    }
+}
+
+pub fn clear_glyph_queue(mut queue: ResMut<GlyphQueue>) {
+    *queue = GlyphQueue::default();
+}
@@ -0,0 +1,169 @@
+use super::{
+    fuzzy::FuzzyRule, glyph::Glyph, keyboard::next_key, layout::TypedEvent, physics::Motion,
+    player::InputCodes, scheme::ActiveScheme,
+};
+use crate::{constants::*, utils::data_dir};
+use bevy::prelude::*;
+use itertools::Itertools;
+use std::{collections::HashMap, fs, io, path::PathBuf};
+
+#[derive(Default, Clone, Copy)]
+pub struct KeyStat {
+    /// Times the key was the next one expected.
+    pub expected: u32,
+    /// Times another key was pressed when this one was expected.
+    pub errors: u32,
+    pub hits: u32,
+    /// Total seconds from the previous keystroke to each hit.
+    pub latency: f32,
+}
+
+impl KeyStat {
+    pub fn average_latency(&self) -> Option<f32> {
+        (self.hits > 0).then(|| self.latency / self.hits as f32)
+    }
+
+    pub fn error_rate(&self) -> Option<f32> {
+        (self.expected > 0).then(|| self.errors as f32 / self.expected as f32)
+    }
+}
+
+#[derive(Default, Clone, Copy)]
+pub struct DigraphStat {
+    pub count: u32,
+    /// Total seconds between the two keys.
+    pub latency: f32,
+}
+
+impl DigraphStat {
+    pub fn average_latency(&self) -> f32 {
+        self.latency / self.count as f32
+    }
+}
+
+#[derive(Default)]
+pub struct SchemeKeyStats {
+    pub keys: HashMap<char, KeyStat>,
+    pub digraphs: HashMap<(char, char), DigraphStat>,
+    /// Correct answers that needed each fuzzy pinyin rule.
+    pub fuzzy: HashMap<FuzzyRule, u32>,
+}
+
+impl SchemeKeyStats {
+    /// Key pairs typed within a code, slowest first.
+    pub fn slow_digraphs(&self) -> impl Iterator<Item = (&(char, char), &DigraphStat)> {
+        self.digraphs
+            .iter()
+            .sorted_by(|(_, a), (_, b)| b.average_latency().total_cmp(&a.average_latency()))
+    }
+
+    /// Writes the key and digraph statistics as CSV, returning the path written to.
+    pub fn export(&self, scheme: &str) -> io::Result<PathBuf> {
+        let dir = data_dir().ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+        let keys = self
+            .keys
+            .iter()
+            .sorted_by_key(|(key, _)| **key)
+            .map(|(key, stat)| {
+                format!(
+                    "key,{key},{},{},{:.0}\n",
+                    stat.expected,
+                    stat.errors,
+                    stat.average_latency().unwrap_or_default() * 1000.0
+                )
+            });
+        let digraphs = self.slow_digraphs().map(|((first, second), stat)| {
+            format!(
+                "digraph,{first}{second},{},,{:.0}\n",
+                stat.count,
+                stat.average_latency() * 1000.0
+            )
+        });
+        let fuzzy = self
+            .fuzzy
+            .iter()
+            .sorted()
+            .map(|(rule, count)| format!("fuzzy,{},{count},,\n", rule.label()));
+        let contents = std::iter::once("type,keys,count,errors,latency_ms\n".to_string())
+            .chain(keys)
+            .chain(digraphs)
+            .chain(fuzzy)
+            .collect::<String>();
+
+        let path = dir.join(format!("{KEY_STATS_FILE_PREFIX}_{scheme}.csv"));
+        fs::create_dir_all(&dir)?;
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+}
+
+/// Keystroke statistics by scheme name.
+#[derive(Default, Resource, Deref, DerefMut)]
+pub struct KeyStats(HashMap<String, SchemeKeyStats>);
+
+/// The previous keystroke of the round and when it was read, which the next one is timed from.
+#[derive(Default, Resource)]
+pub struct LastKeystroke(pub Option<(char, f32)>);
+
+/// Times every typed key against the code of the glyph in play.
+/// Runs before [`handle_input`](super::player::handle_input) so the codes typed so far are those before each key.
+pub fn record_keystroke(
+    scheme: ActiveScheme,
+    mut events: EventReader<TypedEvent>,
+    mut stats: ResMut<KeyStats>,
+    glyph_query: Query<&Glyph, With<Motion>>,
+    input_query: Query<&Text, With<InputCodes>>,
+    mut previous: ResMut<LastKeystroke>,
+) {
+    let mut typed = match input_query.get_single() {
+        Ok(text) => text.sections[0].value.clone(),
+        Err(_) => return,
+    };
+    let code = glyph_query
+        .iter()
+        .next()
+        .and_then(|glyph| scheme.first_code(glyph))
+        .unwrap_or_default();
+    let stats = stats.entry(scheme.name().into()).or_default();
+
+    for event in events.iter() {
+        let key = event.char;
+        if key == ' ' {
+            typed.clear();
+            previous.0 = Some((key, event.timestamp));
+            continue;
+        }
+
+        let expected = next_key(&code, &typed).filter(|expected| *expected != ' ');
+        if let Some(expected) = expected {
+            let stat = stats.keys.entry(expected).or_default();
+            stat.expected += 1;
+            if key != expected {
+                stat.errors += 1;
+            }
+        }
+
+        // keys read in the same frame can't be timed apart
+        let timed = previous
+            .0
+            .filter(|(_, timestamp)| *timestamp < event.timestamp);
+        if expected == Some(key) {
+            if let Some((previous, timestamp)) = timed {
+                let latency = event.timestamp - timestamp;
+                let stat = stats.keys.entry(key).or_default();
+                stat.hits += 1;
+                stat.latency += latency;
+
+                // a pair within the code, not the pause before it
+                if previous != ' ' {
+                    let digraph = stats.digraphs.entry((previous, key)).or_default();
+                    digraph.count += 1;
+                    digraph.latency += latency;
+                }
+            }
+        }
+
+        previous.0 = Some((key, event.timestamp));
+        typed.push(key);
+    }
+}
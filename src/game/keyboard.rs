@@ -0,0 +1,201 @@
+use super::{
+    glyph::Glyph,
+    layout::{KeyLayout, TypedEvent},
+    physics::Motion,
+    player::InputCodes,
+    scheme::ActiveScheme,
+    Cleanup, TypingMode,
+};
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// Whether the on-screen keyboard is shown under the arena.
+#[derive(Default, Resource)]
+pub struct KeyboardOverlay {
+    pub enabled: bool,
+}
+
+#[derive(Component)]
+pub struct KeyboardKey {
+    key: char,
+    /// Remaining flash after the key is pressed, and whether it was the expected key.
+    flash: Option<(Timer, bool)>,
+}
+
+impl KeyboardKey {
+    fn new(key: char) -> Self {
+        Self { key, flash: None }
+    }
+}
+
+pub fn make_keyboard(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    overlay: Res<KeyboardOverlay>,
+    scheme: ActiveScheme,
+    layout: Res<KeyLayout>,
+) {
+    if !overlay.enabled {
+        return;
+    }
+
+    let key_style = Style {
+        size: Size::new(Val::Px(KEYBOARD_KEY_SIZE), Val::Px(KEYBOARD_KEY_SIZE)),
+        margin: UiRect::all(Val::Px(2.0)),
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..Default::default()
+    };
+    let letter_style = TextStyle {
+        font: asset_server.load(FONT_FIRA_MONO),
+        font_size: 16.0,
+        color: Color::WHITE,
+    };
+    let label_style = TextStyle {
+        font: asset_server.load(FONT_LXGW),
+        font_size: 10.0,
+        color: Color::GOLD,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Auto),
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        bottom: Val::Px(8.0),
+                        ..Default::default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: Color::NONE.into(),
+                ..Default::default()
+            },
+            Cleanup,
+        ))
+        .with_children(|parent| {
+            for row in KEYBOARD_ROWS {
+                parent
+                    .spawn(NodeBundle {
+                        background_color: Color::NONE.into(),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        for position in row.chars() {
+                            let key = layout.char_at(position).unwrap_or(position);
+                            parent
+                                .spawn((
+                                    NodeBundle {
+                                        style: key_style.clone(),
+                                        background_color: KEYBOARD_KEY_COLOR.into(),
+                                        ..Default::default()
+                                    },
+                                    KeyboardKey::new(key),
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        key.to_ascii_uppercase(),
+                                        letter_style.clone(),
+                                    ));
+                                    parent.spawn(TextBundle::from_section(
+                                        scheme.key_label(key),
+                                        label_style.clone(),
+                                    ));
+                                });
+                        }
+                    });
+            }
+
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(KEYBOARD_KEY_SIZE * 6.0), Val::Px(16.0)),
+                        ..key_style
+                    },
+                    background_color: KEYBOARD_KEY_COLOR.into(),
+                    ..Default::default()
+                },
+                KeyboardKey::new(' '),
+            ));
+        });
+}
+
+/// The key expected after the codes typed so far, or a space once the code is complete.
+pub fn next_key(code: &str, typed: &str) -> Option<char> {
+    let rest = code.strip_prefix(typed)?;
+    Some(rest.chars().next().unwrap_or(' '))
+}
+
+/// Flashes pressed keys red or green.
+/// Runs before [`handle_input`](super::player::handle_input) so the codes typed so far are those before each key.
+pub fn keyboard_input(
+    scheme: ActiveScheme,
+    mut events: EventReader<TypedEvent>,
+    glyph_query: Query<&Glyph, With<Motion>>,
+    input_query: Query<&Text, With<InputCodes>>,
+    mut query: Query<&mut KeyboardKey>,
+) {
+    let mut typed = match input_query.get_single() {
+        Ok(text) => text.sections[0].value.clone(),
+        Err(_) => return,
+    };
+    let code = glyph_query
+        .iter()
+        .next()
+        .and_then(|glyph| scheme.first_code(glyph))
+        .unwrap_or_default();
+
+    for event in events.iter() {
+        let correct = next_key(&code, &typed) == Some(event.char);
+        for mut key in query.iter_mut() {
+            if key.key.eq_ignore_ascii_case(&event.char) {
+                key.flash = Some((
+                    Timer::from_seconds(KEYBOARD_FLASH_DURATION, TimerMode::Once),
+                    correct,
+                ));
+            }
+        }
+
+        match event.char {
+            ' ' => typed.clear(),
+            char => typed.push(char),
+        }
+    }
+}
+
+/// Colors the keyboard, highlighting the next expected key unless the glyph is being dictated.
+pub fn keyboard_system(
+    time: Res<Time>,
+    scheme: ActiveScheme,
+    typing_mode: Res<TypingMode>,
+    glyph_query: Query<&Glyph, With<Motion>>,
+    input_query: Query<&Text, With<InputCodes>>,
+    mut query: Query<(&mut KeyboardKey, &mut BackgroundColor)>,
+) {
+    let next = match (glyph_query.iter().next(), input_query.get_single()) {
+        (Some(glyph), Ok(text)) if *typing_mode != TypingMode::Dictation => scheme
+            .first_code(glyph)
+            .and_then(|code| next_key(&code, &text.sections[0].value)),
+        _ => None,
+    };
+
+    for (mut key, mut color) in query.iter_mut() {
+        if let Some((timer, _)) = &mut key.flash {
+            if timer.tick(time.delta()).finished() {
+                key.flash = None;
+            }
+        }
+
+        *color = match key.flash {
+            Some((_, true)) => KEYBOARD_CORRECT_COLOR,
+            Some((_, false)) => KEYBOARD_WRONG_COLOR,
+            None if next == Some(key.key) => KEYBOARD_NEXT_COLOR,
+            None => KEYBOARD_KEY_COLOR,
+        }
+        .into();
+    }
+}
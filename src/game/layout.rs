@@ -0,0 +1,328 @@
+use super::{player::InputCodes, scheme::ActiveScheme, Cleanup, TypingMode};
+use crate::constants::*;
+use bevy::{input::keyboard::KeyboardInput, input::ButtonState, prelude::*};
+use itertools::Itertools;
+
+/// How keystrokes are turned into typed characters.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyLayout {
+    /// Characters as produced by the OS, including its layout and IME.
+    #[default]
+    Text,
+    /// Physical keys read by position, then mapped to a logical layout.
+    Qwerty,
+    Dvorak,
+    Colemak,
+    Azerty,
+}
+
+/// Key positions, named after the QWERTY characters on them.
+const QWERTY_KEYS: &str = "qwertyuiop[]asdfghjkl;'zxcvbnm,./1\\";
+const DVORAK_KEYS: &str = "',.pyfgcrl/=aoeuidhtns-;qjkxbmwvz1\\";
+const COLEMAK_KEYS: &str = "qwfpgjluy;[]arstdhneio'zxcvbkm,./1\\";
+const AZERTY_KEYS: &str = "azertyuiop^$qsdfghjklmùwxcvbn,;:!&*";
+
+/// Fingers for the keys in [`QWERTY_KEYS`] as indices into [`Finger::ALL`],
+/// following standard touch typing.
+const QWERTY_FINGERS: &str = "01233556788801233556788012335567808";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Finger {
+    LeftPinky,
+    LeftRing,
+    LeftMiddle,
+    LeftIndex,
+    Thumb,
+    RightIndex,
+    RightMiddle,
+    RightRing,
+    RightPinky,
+}
+
+impl Finger {
+    pub const ALL: [Finger; 9] = [
+        Finger::LeftPinky,
+        Finger::LeftRing,
+        Finger::LeftMiddle,
+        Finger::LeftIndex,
+        Finger::Thumb,
+        Finger::RightIndex,
+        Finger::RightMiddle,
+        Finger::RightRing,
+        Finger::RightPinky,
+    ];
+
+    /// Short label, counting fingers from the thumb, e.g. `L4` for the left pinky.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Finger::LeftPinky => "L4",
+            Finger::LeftRing => "L3",
+            Finger::LeftMiddle => "L2",
+            Finger::LeftIndex => "L1",
+            Finger::Thumb => "T",
+            Finger::RightIndex => "R1",
+            Finger::RightMiddle => "R2",
+            Finger::RightRing => "R3",
+            Finger::RightPinky => "R4",
+        }
+    }
+}
+
+/// Characters typed with Shift, in pairs of unshifted and shifted.
+const US_SHIFTED: &str = "1!;:/?'\",<.>";
+const AZERTY_SHIFTED: &str = "&1,?;.:/!§ù%";
+
+impl KeyLayout {
+    pub const ALL: [KeyLayout; 5] = [
+        KeyLayout::Text,
+        KeyLayout::Qwerty,
+        KeyLayout::Dvorak,
+        KeyLayout::Colemak,
+        KeyLayout::Azerty,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyLayout::Text => "text",
+            KeyLayout::Qwerty => "qwerty",
+            KeyLayout::Dvorak => "dvorak",
+            KeyLayout::Colemak => "colemak",
+            KeyLayout::Azerty => "azerty",
+        }
+    }
+
+    /// Characters on the keys, in the order of [`QWERTY_KEYS`].
+    fn keys(&self) -> &'static str {
+        match self {
+            KeyLayout::Text | KeyLayout::Qwerty => QWERTY_KEYS,
+            KeyLayout::Dvorak => DVORAK_KEYS,
+            KeyLayout::Colemak => COLEMAK_KEYS,
+            KeyLayout::Azerty => AZERTY_KEYS,
+        }
+    }
+
+    fn shifted_pairs(&self) -> &'static str {
+        match self {
+            KeyLayout::Azerty => AZERTY_SHIFTED,
+            _ => US_SHIFTED,
+        }
+    }
+
+    /// The character typed on the key at a QWERTY position.
+    pub fn char_at(&self, position: char) -> Option<char> {
+        let index = QWERTY_KEYS.chars().position(|key| key == position)?;
+        self.keys().chars().nth(index)
+    }
+
+    /// The finger that types a character, following the key it's on in this layout.
+    pub fn finger(&self, char: char) -> Option<Finger> {
+        if char == ' ' {
+            return Some(Finger::Thumb);
+        }
+
+        // shifted characters are typed on the key of their unshifted one
+        let char = self
+            .shifted_pairs()
+            .chars()
+            .tuples()
+            .find(|(_, shifted)| *shifted == char)
+            .map_or(char.to_ascii_lowercase(), |(key, _)| key);
+        let index = self.keys().chars().position(|key| key == char)?;
+        let finger = QWERTY_FINGERS.chars().nth(index)?.to_digit(10)?;
+        Finger::ALL.get(finger as usize).copied()
+    }
+
+    /// The character typed on a key while Shift is held.
+    pub fn shifted(&self, char: char) -> char {
+        self.shifted_pairs()
+            .chars()
+            .tuples()
+            .find(|(key, _)| *key == char)
+            .map_or(char.to_ascii_uppercase(), |(_, shifted)| shifted)
+    }
+}
+
+/// QWERTY position of a scan code, or a space for the space bar.
+#[cfg(not(any(target_os = "macos", target_arch = "wasm32")))]
+fn physical_key(event: &KeyboardInput) -> Option<char> {
+    let scan_code = event.scan_code;
+    match scan_code {
+        16..=27 => QWERTY_KEYS.chars().nth(scan_code as usize - 16),
+        30..=40 => QWERTY_KEYS.chars().nth(scan_code as usize - 30 + 12),
+        44..=53 => QWERTY_KEYS.chars().nth(scan_code as usize - 44 + 23),
+        2 => Some('1'),
+        43 => Some('\\'),
+        57 => Some(' '),
+        _ => None,
+    }
+}
+
+/// QWERTY position of a macOS virtual key code, or a space for the space bar.
+#[cfg(target_os = "macos")]
+fn physical_key(event: &KeyboardInput) -> Option<char> {
+    const KEYS: &str = "asdfhgzxcv bqweryt123465=97-80]ou[ip\rlj'k;\\,/nm.";
+    match event.scan_code {
+        49 => Some(' '),
+        scan_code => KEYS
+            .chars()
+            .nth(scan_code as usize)
+            .filter(|key| QWERTY_KEYS.contains(*key)),
+    }
+}
+
+/// QWERTY position of a key, or a space for the space bar.
+/// Browsers report the layout-dependent key code as the scan code, so the position comes from the
+/// key code instead, which follows the physical key.
+#[cfg(target_arch = "wasm32")]
+fn physical_key(event: &KeyboardInput) -> Option<char> {
+    use KeyCode::*;
+    const CODES: [KeyCode; 35] = [
+        Q, W, E, R, T, Y, U, I, O, P, LBracket, RBracket, A, S, D, F, G, H, J, K, L, Semicolon,
+        Apostrophe, Z, X, C, V, B, N, M, Comma, Period, Slash, Key1, Backslash,
+    ];
+    match event.key_code? {
+        Space => Some(' '),
+        key_code => CODES
+            .iter()
+            .position(|code| *code == key_code)
+            .and_then(|index| QWERTY_KEYS.chars().nth(index)),
+    }
+}
+
+/// A character typed by the player, from either input path.
+pub struct TypedEvent {
+    pub char: char,
+    /// Seconds since startup when the key was read.
+    /// Winit doesn't timestamp input, so keys read in the same frame share one.
+    pub timestamp: f32,
+}
+
+/// Sends [`TypedEvent`] from OS characters or physical keys, depending on the [`KeyLayout`].
+/// Characters that can't be part of a code in the active scheme are dropped and reported.
+/// Keys are only read in a game, while there are codes to type them into.
+#[allow(clippy::too_many_arguments)]
+pub fn read_keys(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    layout: Res<KeyLayout>,
+    scheme: ActiveScheme,
+    typing_mode: Res<TypingMode>,
+    mut character_events: EventReader<ReceivedCharacter>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut typed_events: EventWriter<TypedEvent>,
+    warnings: Query<Entity, With<InputWarning>>,
+    input_query: Query<(), With<InputCodes>>,
+    mut shift: Local<bool>,
+) {
+    if input_query.is_empty() {
+        character_events.clear();
+        keyboard_events.clear();
+        return;
+    }
+
+    // apostrophes separate the codes of a sentence
+    let accepts = |char: char| {
+        char == ' '
+            || (char == '\'' && *typing_mode == TypingMode::Sentence)
+            || scheme.accepts(char)
+    };
+    let typed = |char| TypedEvent {
+        char,
+        timestamp: time.elapsed_seconds(),
+    };
+    let mut foreign = None;
+    match *layout {
+        KeyLayout::Text => {
+            keyboard_events.clear();
+            for event in character_events.iter() {
+                if accepts(event.char) {
+                    typed_events.send(typed(event.char));
+                } else if !event.char.is_control() {
+                    foreign = Some(event.char);
+                }
+            }
+        }
+        _ => {
+            character_events.clear();
+            for event in keyboard_events.iter() {
+                if matches!(event.key_code, Some(KeyCode::LShift | KeyCode::RShift)) {
+                    *shift = event.state == ButtonState::Pressed;
+                }
+                if event.state != ButtonState::Pressed {
+                    continue;
+                }
+                // keys off the typing area, such as arrows, are ignored
+                let char = physical_key(event).and_then(|position| match position {
+                    ' ' => Some(' '),
+                    position if *shift => layout.char_at(position).map(|c| layout.shifted(c)),
+                    position => layout.char_at(position),
+                });
+                match char {
+                    Some(char) if accepts(char) => typed_events.send(typed(char)),
+                    Some(char) => foreign = Some(char),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    if let Some(char) = foreign {
+        for entity in warnings.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        let advice = match *layout {
+            KeyLayout::Text => "Turn off the IME or pick a physical layout in Settings.",
+            _ => "Release Shift or pick another layout in Settings.",
+        };
+        let message = format!(
+            "\"{char}\" is not part of {} codes.\n{advice}",
+            scheme.name()
+        );
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: UiRect {
+                            left: Val::Px(16.0),
+                            top: Val::Px(16.0),
+                            ..Default::default()
+                        },
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..Default::default()
+                    },
+                    background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+                    ..Default::default()
+                },
+                InputWarning(Timer::from_seconds(INPUT_WARNING_DURATION, TimerMode::Once)),
+                Cleanup,
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    message,
+                    TextStyle {
+                        font: asset_server.load(FONT_LXGW),
+                        font_size: 16.0,
+                        color: Color::GOLD,
+                    },
+                ));
+            });
+    }
+}
+
+#[derive(Component)]
+pub struct InputWarning(Timer);
+
+pub fn input_warning(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut InputWarning)>,
+) {
+    for (entity, mut warning) in query.iter_mut() {
+        if warning.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -1,26 +1,66 @@
 use self::{
-    ball::*, base::*, battle::*, enemy::*, hint::*, physics::*, player::*, practice::*, slits::*,
+    ball::*, base::*, battle::*, deck::*, enemy::*, fingers::*, fuzzy::*, gloss::*, glyph::*,
+    hint::*, key_stats::*, keyboard::*, layout::*, physics::*, player::*, practice::*, review::*,
+    rime::*, sentence::*, slits::*, sprint::*, word_list::*,
 };
 use crate::{
     constants::*,
     effects::*,
-    utils::{cleanup_system, escape_system, Damp, Intermediate},
-    AppState, AudioVolume, MusicTrack, TimeScale,
+    utils::{cleanup_system, escape_system, Damp, Shape},
+    AppState, MusicTrack, TimeScale,
 };
 use bevy::{prelude::*, sprite::MaterialMesh2dBundle, time::FixedTimestep};
-use bevy_kira_audio::{Audio, AudioApp, AudioChannel, AudioControl, AudioSource};
 use itertools::Itertools;
 use std::f32::consts::FRAC_PI_4;
 
+#[cfg(feature = "audio")]
+mod audio;
 mod ball;
 mod base;
 mod battle;
+mod deck;
 mod enemy;
+mod field;
+mod fingers;
+mod fuzzy;
+mod gloss;
+mod glyph;
 mod hint;
+mod key_stats;
+mod keyboard;
+mod layout;
 mod physics;
 mod player;
 mod practice;
+mod punctuation;
+mod review;
+mod rime;
+mod scheme;
+mod sentence;
+mod simulation;
 mod slits;
+mod sprint;
+mod word_list;
+
+pub use deck::{CharacterSet, Decks};
+pub use field::{Force, ForceField};
+pub use fingers::{FingerGuide, FingerStats};
+pub use fuzzy::{FuzzyPinyin, FuzzyRule};
+pub use gloss::GlossDisplay;
+pub use glyph::{Glyph, GlyphQueue, GlyphTable};
+pub use key_stats::KeyStats;
+pub use keyboard::KeyboardOverlay;
+pub use layout::{Finger, KeyLayout};
+pub use physics::{
+    physics_systems, CollisionEvent, ContactEvent, ContactPhase, Motion, PhysicsLayers, RigidBody,
+};
+pub use punctuation::{mix_punctuation, punctuation_glyphs, PunctuationMix};
+pub use review::Review;
+pub use scheme::{ActiveScheme, Scheme};
+pub use sentence::{sentences, Sentence};
+pub use simulation::{simulate, Simulation, SimulationMode, SimulationResult};
+pub use sprint::{Leaderboard, Sprint};
+pub use word_list::WordList;
 
 pub struct GamePlugin;
 
@@ -29,47 +69,76 @@ impl Plugin for GamePlugin {
         app.add_event::<GameOverEvent>()
             .add_event::<PlayerHitEvent>()
             .add_event::<PlayerMissEvent>()
-            .add_event::<BounceEvent>()
             .add_event::<HealEvent>()
+            .add_event::<TypedEvent>()
             .add_event::<ConfirmEvent>()
-            .insert_resource(Debounce {
-                audio_bounce_long: Timer::from_seconds(0.5, TimerMode::Once),
-                audio_bounce_short: Timer::from_seconds(0.1, TimerMode::Once),
-                audio_hit: Timer::from_seconds(0.1, TimerMode::Once),
-                bounce: Timer::from_seconds(0.1, TimerMode::Once),
-                effects: Timer::from_seconds(0.1, TimerMode::Once),
-                hit: Timer::from_seconds(0.1, TimerMode::Once),
-                miss: Timer::from_seconds(0.5, TimerMode::Once),
-            })
+            .add_event::<AnswerEvent>()
+            .add_event::<DroppedFile>()
+            .init_resource::<Debounce>()
             .init_resource::<Score>()
+            .init_resource::<TypingMode>()
+            .init_resource::<Scheme>()
+            .init_resource::<RimeScheme>()
+            .init_resource::<KeyboardOverlay>()
+            .init_resource::<KeyLayout>()
+            .init_resource::<KeyStats>()
+            .init_resource::<LastKeystroke>()
+            .init_resource::<FingerStats>()
+            .init_resource::<FingerGuide>()
+            .init_resource::<FuzzyPinyin>()
+            .init_resource::<GlossDisplay>()
+            .init_resource::<Dictionary>()
+            .init_resource::<PunctuationMix>()
+            .init_resource::<Review>()
+            .init_resource::<GlyphQueue>()
+            .init_resource::<Sentence>()
+            .init_resource::<GlyphTable>()
+            .init_resource::<WordList>()
+            .init_resource::<Decks>()
+            .init_resource::<CharacterSet>()
             .init_resource::<Slits>()
-            .add_audio_channel::<BounceAudioChannel>()
-            .add_audio_channel::<ScoreAudioChannel>()
             .add_startup_system(setup_game)
+            .add_startup_system(load_word_list)
+            .add_startup_system(load_decks)
+            .add_startup_system(load_dictionary)
+            .add_startup_system(load_rime)
+            .add_system_set(play_systems())
+            .add_system_set(ai_systems())
             .add_system_set(
                 SystemSet::new()
-                    // fundamental game-play systems
-                    .with_system(move_player)
-                    .with_system(handle_input)
-                    .with_system(add_location_target)
-                    .with_system(assist_player)
-                    .with_system(move_enemy)
-                    .with_system(move_ball)
-                    .with_system(activate_ball)
+                    .with_system(read_keys.before(keyboard_input).before(handle_input))
+                    .with_system(keyboard_input.before(handle_input))
+                    .with_system(record_keystroke.after(read_keys).before(handle_input))
+                    .with_system(record_fingers.after(read_keys).before(handle_input))
+                    .with_system(finger_hint)
+                    .with_system(segment_input.after(handle_input).before(check_answer))
+                    .with_system(sentence_system.after(advance_glyph))
+                    .with_system(input_warning)
+                    .with_system(keyboard_system)
                     .with_system(update_ball)
-                    .with_system(ball_bounce)
-                    .with_system(heal_enemy_base)
-                    .with_system(move_slit_block)
-                    .with_system(slits_system)
                     // effects and juice
-                    .with_system(game_over_slow_motion)
-                    .with_system(bounce_audio)
-                    .with_system(score_audio)
                     .with_system(score_effects)
                     .with_system(bounce_effects)
+                    .with_system(reveal_glyph.after(check_answer))
+                    // answers and review
+                    .with_system(start_attempts)
+                    .with_system(record_answer)
+                    .with_system(record_fuzzy)
+                    .with_system(advance_glyph.after(record_answer).after(reveal_glyph))
+                    // word list import
+                    .with_system(
+                        read_dropped_files
+                            .before(import_word_list)
+                            .before(import_deck),
+                    )
+                    .with_system(import_word_list)
+                    .with_system(import_rime)
+                    .with_system(import_deck)
+                    .with_system(import_panel)
+                    .with_system(show_gloss.before(advance_glyph))
+                    .with_system(gloss_panel)
                     // score and display
                     .with_system(count_ball)
-                    .with_system(score_system)
                     .with_system(health_bar)
                     .with_system(health_bar_tracker)
                     // hints
@@ -77,23 +146,44 @@ impl Plugin for GamePlugin {
                     .with_system(make_ball_hint)
                     .with_system(hint_system),
             )
-            .add_system_set(
-                SystemSet::new()
-                    .with_run_criteria(FixedTimestep::step(AI_TIME_STEP as f64))
-                    .with_system(predict_ball)
-                    .with_system(control_enemy),
-            )
             .add_plugin(PhysicsPlugin)
             .add_plugin(BattlePlugin)
-            .add_plugin(PracticePlugin);
+            .add_plugin(PracticePlugin)
+            .add_plugin(SprintPlugin);
+
+        #[cfg(feature = "audio")]
+        app.add_plugin(audio::GameAudioPlugin);
     }
 }
 
-#[derive(Resource)]
-struct BounceAudioChannel;
+/// Game-play systems of every mode, shared with [`simulate`].
+/// Each comes after the one before it, since the order of systems that don't depend on each other
+/// changes from run to run.
+fn play_systems() -> SystemSet {
+    SystemSet::new()
+        .with_system(handle_input)
+        .with_system(check_answer.after(handle_input))
+        .with_system(move_player.after(check_answer))
+        .with_system(assist_player.after(move_player))
+        .with_system(move_enemy.after(assist_player))
+        .with_system(move_ball.after(move_enemy))
+        .with_system(activate_ball.after(move_ball))
+        .with_system(spin_ball.after(activate_ball))
+        .with_system(heal_enemy_base.after(spin_ball))
+        .with_system(move_slit_block.after(heal_enemy_base))
+        .with_system(slits_system.after(move_slit_block))
+        .with_system(game_over_slow_motion.after(slits_system))
+        .with_system(score_system.after(game_over_slow_motion))
+}
 
-#[derive(Resource)]
-struct ScoreAudioChannel;
+/// The enemy and the player assist, steered by the predicted ball at a lower rate.
+fn ai_systems() -> SystemSet {
+    SystemSet::new()
+        .with_run_criteria(FixedTimestep::step(AI_TIME_STEP as f64))
+        .before(move_player)
+        .with_system(predict_ball)
+        .with_system(control_enemy.after(predict_ball))
+}
 
 #[derive(Clone, Copy)]
 enum GameOverEvent {
@@ -113,25 +203,23 @@ struct PlayerMissEvent {
     lose: bool,
 }
 
-#[allow(dead_code)]
-struct BounceEvent {
-    ball: Entity,
-    other: Entity,
-    location: Vec2,
-}
-
 #[derive(Resource)]
 struct Debounce {
-    audio_bounce_long: Timer,
-    audio_bounce_short: Timer,
-    audio_hit: Timer,
-
-    bounce: Timer,
     effects: Timer,
     hit: Timer,
     miss: Timer,
 }
 
+impl Default for Debounce {
+    fn default() -> Self {
+        Self {
+            effects: Timer::from_seconds(0.1, TimerMode::Once),
+            hit: Timer::from_seconds(0.1, TimerMode::Once),
+            miss: Timer::from_seconds(0.5, TimerMode::Once),
+        }
+    }
+}
+
 #[derive(Resource)]
 struct GameOver {
     slow_motion_timer: Timer,
@@ -157,6 +245,8 @@ pub struct Score {
     pub timestamp: f32,
     pub hits: i32,
     pub miss: i32,
+    /// Correctly answered glyphs.
+    pub answers: i32,
 }
 
 impl FromWorld for Score {
@@ -166,13 +256,29 @@ impl FromWorld for Score {
             timestamp: time.elapsed_seconds(),
             hits: 0,
             miss: 0,
+            answers: 0,
         }
     }
 }
 
+/// How glyphs are presented to the player.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TypingMode {
+    #[default]
+    Normal,
+    /// The glyph is hidden and only pronounced; it's revealed once the answer is confirmed.
+    Dictation,
+    /// A few glyphs are shown at once and typed as continuous codes, see [`Sentence`].
+    Sentence,
+}
+
 #[derive(Component)]
 struct Cleanup;
 
+/// Sensor covering the arena; a ball that stops touching it has left the arena.
+#[derive(Component)]
+struct Arena;
+
 #[derive(Clone, Copy, PartialEq, Eq, Component)]
 enum BounceAudio {
     Bounce,
@@ -189,15 +295,6 @@ struct Materials {
     hit: Handle<TextureAtlas>,
 }
 
-#[derive(Resource)]
-struct Audios {
-    hit_audio: Handle<AudioSource>,
-    miss_audio: Handle<AudioSource>,
-    explosion_audio: Handle<AudioSource>,
-    lose_audio: Handle<AudioSource>,
-    impact_audios: Vec<Handle<AudioSource>>,
-}
-
 fn setup_game(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -219,35 +316,77 @@ fn setup_game(
         )),
     });
 
-    commands.insert_resource(Audios {
-        hit_audio: asset_server.load(HIT_AUDIO),
-        miss_audio: asset_server.load(MISS_AUDIO),
-        explosion_audio: asset_server.load(EXPLOSION_AUDIO),
-        lose_audio: asset_server.load(LOSE_AUDIO),
-        impact_audios: IMPACT_AUDIOS
-            .iter()
-            .map(|path| asset_server.load(*path))
-            .collect_vec(),
-    });
-
     commands.init_resource::<Score>();
 }
 
+// bodies shared with `simulate`, each with the transform to spawn it at
+
+fn arena_body() -> (Transform, (RigidBody, PhysicsLayers, Arena)) {
+    let body = RigidBody::new(
+        Shape::Aabb(Vec2::new(ARENA_WIDTH, ARENA_HEIGHT)),
+        0.0,
+        0.0,
+        0.0,
+    );
+    (
+        Transform::default(),
+        (body.sensor(), PhysicsLayers::ARENA, Arena),
+    )
+}
+
+fn separate_body() -> (Transform, (RigidBody, PhysicsLayers)) {
+    let body = RigidBody::new(Shape::Aabb(HORIZONTAL_BOUNDARY_SIZE), 0.0, 0.9, 0.5);
+    (
+        Transform::from_xyz(0.0, 8.0, 0.0),
+        (body, PhysicsLayers::SEPARATE),
+    )
+}
+
+fn top_boundary_body() -> (Transform, (RigidBody, PhysicsLayers, EnemyBase)) {
+    let body = RigidBody::new(Shape::Aabb(HORIZONTAL_BOUNDARY_SIZE), 0.0, 0.9, 0.0);
+    (
+        Transform::from_xyz(0.0, ARENA_HEIGHT * 0.5 + 16.0, 0.0),
+        (body, PhysicsLayers::BOUNDARY, EnemyBase::default()),
+    )
+}
+
+fn bottom_boundary_body() -> (Transform, (RigidBody, PhysicsLayers, PlayerBase)) {
+    let body = RigidBody::new(Shape::Aabb(HORIZONTAL_BOUNDARY_SIZE), 0.0, 0.9, 0.5);
+    (
+        Transform::from_xyz(0.0, -ARENA_HEIGHT * 0.5 - 16.0, 0.0),
+        (body, PhysicsLayers::BOUNDARY, PlayerBase::default()),
+    )
+}
+
+/// The left boundary for a `side` of -1, the right one for 1.
+fn side_boundary_body(side: f32) -> (Transform, (RigidBody, PhysicsLayers)) {
+    let body = RigidBody::new(Shape::Aabb(VERTICAL_BOUNDARY_SIZE), 0.0, 1.0, 0.0);
+    (
+        Transform::from_xyz(side * (ARENA_WIDTH * 0.5 + 16.0), 0.0, 0.0),
+        (body, PhysicsLayers::BOUNDARY),
+    )
+}
+
 fn make_arena(mut commands: Commands) {
+    let boundary_sprite = |size| Sprite {
+        color: BOUNDARY_COLOR,
+        custom_size: Some(size),
+        ..Default::default()
+    };
+
+    let (transform, body) = arena_body();
+    commands.spawn((TransformBundle::from_transform(transform), body, Cleanup));
+
     // middle Separate
+    let (transform, body) = separate_body();
     commands
         .spawn((
             SpriteBundle {
-                transform: Transform::from_xyz(0.0, 8.0, 0.0),
-                sprite: Sprite {
-                    color: BOUNDARY_COLOR,
-                    custom_size: Some(Vec2::new(ARENA_WIDTH, 32.0)),
-                    ..Default::default()
-                },
+                transform,
+                sprite: boundary_sprite(HORIZONTAL_BOUNDARY_SIZE),
                 ..Default::default()
             },
-            RigidBody::new(Vec2::new(ARENA_WIDTH, 32.0), 0.0, 0.9, 0.5),
-            PhysicsLayers::SEPARATE,
+            body,
             Cleanup,
         ))
         .with_children(|parent| {
@@ -263,73 +402,44 @@ fn make_arena(mut commands: Commands) {
         });
 
     // top boundary
+    let (transform, body) = top_boundary_body();
     commands.spawn((
         SpriteBundle {
-            transform: Transform::from_xyz(0.0, ARENA_HEIGHT * 0.5 + 16.0, 0.0),
-            sprite: Sprite {
-                color: BOUNDARY_COLOR,
-                custom_size: Some(Vec2::new(ARENA_WIDTH, 32.0)),
-                ..Default::default()
-            },
+            transform,
+            sprite: boundary_sprite(HORIZONTAL_BOUNDARY_SIZE),
             ..Default::default()
         },
-        RigidBody::new(Vec2::new(ARENA_WIDTH, 32.0), 0.0, 0.9, 0.0),
-        PhysicsLayers::BOUNDARY,
+        body,
         BounceAudio::Hit,
-        EnemyBase::default(),
         Cleanup,
     ));
 
     // bottom boundary
+    let (transform, body) = bottom_boundary_body();
     commands.spawn((
         SpriteBundle {
-            transform: Transform::from_xyz(0.0, -ARENA_HEIGHT * 0.5 - 16.0, 0.0),
-            sprite: Sprite {
-                color: BOUNDARY_COLOR,
-                custom_size: Some(Vec2::new(ARENA_WIDTH, 32.0)),
-                ..Default::default()
-            },
-            ..Default::default()
-        },
-        RigidBody::new(Vec2::new(ARENA_WIDTH, 32.0), 0.0, 0.9, 0.5),
-        PhysicsLayers::BOUNDARY,
-        PlayerBase::default(),
-        Cleanup,
-    ));
-
-    // left boundary
-    commands.spawn((
-        SpriteBundle {
-            transform: Transform::from_xyz(-ARENA_WIDTH * 0.5 - 16.0, 0.0, 0.0),
-            sprite: Sprite {
-                color: BOUNDARY_COLOR,
-                custom_size: Some(Vec2::new(32.0, ARENA_HEIGHT + 64.0)),
-                ..Default::default()
-            },
+            transform,
+            sprite: boundary_sprite(HORIZONTAL_BOUNDARY_SIZE),
             ..Default::default()
         },
-        RigidBody::new(Vec2::new(32.0, ARENA_HEIGHT + 64.0), 0.0, 1.0, 0.0),
-        PhysicsLayers::BOUNDARY,
-        BounceAudio::Bounce,
+        body,
         Cleanup,
     ));
 
-    // right boundary
-    commands.spawn((
-        SpriteBundle {
-            transform: Transform::from_xyz(ARENA_WIDTH * 0.5 + 16.0, 0.0, 0.0),
-            sprite: Sprite {
-                color: BOUNDARY_COLOR,
-                custom_size: Some(Vec2::new(32.0, ARENA_HEIGHT + 64.0)),
+    // left and right boundaries
+    for side in [-1.0, 1.0] {
+        let (transform, body) = side_boundary_body(side);
+        commands.spawn((
+            SpriteBundle {
+                transform,
+                sprite: boundary_sprite(VERTICAL_BOUNDARY_SIZE),
                 ..Default::default()
             },
-            ..Default::default()
-        },
-        RigidBody::new(Vec2::new(32.0, ARENA_HEIGHT + 64.0), 0.0, 1.0, 0.0),
-        PhysicsLayers::BOUNDARY,
-        BounceAudio::Bounce,
-        Cleanup,
-    ));
+            body,
+            BounceAudio::Bounce,
+            Cleanup,
+        ));
+    }
 }
 
 fn make_ui(mut commands: Commands, materials: Res<Materials>, asset_server: Res<AssetServer>) {
@@ -433,11 +543,39 @@ fn make_ui(mut commands: Commands, materials: Res<Materials>, asset_server: Res<
         });
 }
 
+#[allow(clippy::type_complexity)]
+fn player_body() -> (
+    Transform,
+    (
+        RigidBody,
+        Motion,
+        PhysicsLayers,
+        Controller,
+        MotionOverride,
+        Player,
+        PlayerAssist,
+    ),
+) {
+    (
+        Transform::from_xyz(0.0, -160.0, 0.0),
+        (
+            RigidBody::new(PADDLE_SHAPE, 3.0, 2.0, 1.0),
+            Motion::default(),
+            PhysicsLayers::PLAYER,
+            Controller::default(),
+            MotionOverride::default(),
+            Player::default(),
+            PlayerAssist::default(),
+        ),
+    )
+}
+
 fn make_player(mut commands: Commands, materials: Res<Materials>, asset_server: Res<AssetServer>) {
+    let (transform, body) = player_body();
     commands
         .spawn((
             SpriteBundle {
-                transform: Transform::from_xyz(0.0, -160.0, 0.0),
+                transform,
                 sprite: Sprite {
                     custom_size: Some(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT)),
                     color: PADDLE_COLOR,
@@ -445,30 +583,43 @@ fn make_player(mut commands: Commands, materials: Res<Materials>, asset_server:
                 },
                 ..Default::default()
             },
-            RigidBody::new(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT), 3.0, 2.0, 1.0),
-            Motion::default(),
-            PhysicsLayers::PLAYER,
+            body,
             BounceAudio::Bounce,
-            Controller::default(),
-            MotionOverride::default(),
-            Player::default(),
-            PlayerAssist::default(),
             Cleanup,
         ))
         .with_children(|parent| {
-            parent.spawn(Text2dBundle {
-                text: Text::from_section(
-                    "ro",
-                    TextStyle {
-                        font: asset_server.load(FONT_LXGW),
-                        font_size: 24.0,
-                        color: Color::WHITE,
-                    },
-                )
-                .with_alignment(TextAlignment::CENTER),
-                transform: Transform::from_xyz(0.0, 0.0, 0.0),
-                ..Default::default()
-            }).insert(InputCodes);
+            parent
+                .spawn(Text2dBundle {
+                    text: Text::from_section(
+                        "ro",
+                        TextStyle {
+                            font: asset_server.load(FONT_LXGW),
+                            font_size: 24.0,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_alignment(TextAlignment::CENTER),
+                    transform: Transform::from_xyz(0.0, 0.0, 0.0),
+                    ..Default::default()
+                })
+                .insert(InputCodes);
+
+            parent.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load(FONT_FIRA_MONO),
+                            font_size: 14.0,
+                            color: Color::GOLD,
+                        },
+                    )
+                    .with_alignment(TextAlignment::CENTER),
+                    transform: Transform::from_xyz(0.0, -PADDLE_HEIGHT * 1.5, 0.0),
+                    ..Default::default()
+                },
+                FingerHint,
+            ));
         })
         .with_children(|parent| {
             parent.spawn(SpriteBundle {
@@ -485,11 +636,28 @@ fn make_player(mut commands: Commands, materials: Res<Materials>, asset_server:
         });
 }
 
+fn enemy_body() -> (
+    Transform,
+    (RigidBody, Motion, PhysicsLayers, Controller, Enemy),
+) {
+    (
+        Transform::from_xyz(0.0, 160.0, 0.0),
+        (
+            RigidBody::new(PADDLE_SHAPE, 3.0, 1.0, 1.0),
+            Motion::default(),
+            PhysicsLayers::PLAYER,
+            Controller::default(),
+            Enemy::default(),
+        ),
+    )
+}
+
 fn make_enemy(mut commands: Commands, materials: Res<Materials>) {
+    let (transform, body) = enemy_body();
     commands
         .spawn((
             SpriteBundle {
-                transform: Transform::from_xyz(0.0, 160.0, 0.0),
+                transform,
                 sprite: Sprite {
                     custom_size: Some(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT)),
                     color: PADDLE_COLOR,
@@ -497,12 +665,8 @@ fn make_enemy(mut commands: Commands, materials: Res<Materials>) {
                 },
                 ..Default::default()
             },
-            RigidBody::new(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT), 3.0, 1.0, 1.0),
-            Motion::default(),
-            PhysicsLayers::PLAYER,
+            body,
             BounceAudio::Bounce,
-            Controller::default(),
-            Enemy::default(),
             Cleanup,
         ))
         .with_children(|parent| {
@@ -520,13 +684,40 @@ fn make_enemy(mut commands: Commands, materials: Res<Materials>) {
         });
 }
 
-fn make_ball(mut commands: Commands, materials: Res<Materials>, asset_server: Res<AssetServer>) {
+fn ball_body() -> (Transform, (RigidBody, PhysicsLayers, Ball, Trajectory)) {
+    (
+        Transform::from_xyz(0.0, 0.0, 0.0),
+        (
+            RigidBody::new(Shape::Circle(BALL_SIZE / 2.0), 1.0, 1.0, 0.5),
+            PhysicsLayers::BALL,
+            Ball::default(),
+            Trajectory::default(),
+        ),
+    )
+}
+
+fn make_ball(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    typing_mode: Res<TypingMode>,
+    queue: Res<GlyphQueue>,
+) {
     let alpha = 1.0 / BALL_GHOSTS_COUNT as f32;
+    let glyph = queue
+        .glyphs
+        .first()
+        .cloned()
+        .unwrap_or_else(|| Glyph::new("中", "zhong1", &["zhong"]));
+    let value = match *typing_mode {
+        TypingMode::Normal | TypingMode::Sentence => glyph.character.clone(),
+        TypingMode::Dictation => DICTATION_PLACEHOLDER.into(),
+    };
+    let (transform, body) = ball_body();
     commands
         .spawn((
             Text2dBundle {
                 text: Text::from_section(
-                    "中",
+                    value,
                     TextStyle {
                         font: asset_server.load(FONT_LXGW),
                         font_size: 24.0,
@@ -534,14 +725,13 @@ fn make_ball(mut commands: Commands, materials: Res<Materials>, asset_server: Re
                     },
                 )
                 .with_alignment(TextAlignment::CENTER),
-                transform: Transform::from_xyz(0.0, 0.0, 0.0),
+                transform,
                 ..Default::default()
             },
-            RigidBody::new(Vec2::new(BALL_SIZE, BALL_SIZE), 1.0, 1.0, 0.5),
-            PhysicsLayers::BALL,
+            body,
             BounceAudio::Bounce,
-            Ball::default(),
-            Trajectory::default(),
+            glyph,
+            Attempts::default(),
             Cleanup,
         ))
         .with_children(|parent| {
@@ -563,11 +753,13 @@ fn reset_ball(
     mut commands: Commands,
     mut player_miss_events: EventReader<PlayerMissEvent>,
     mut player_hit_events: EventReader<PlayerHitEvent>,
+    mut contact_events: EventReader<ContactEvent>,
     mut time_scale: ResMut<TimeScale>,
-    mut query: Query<(Entity, &mut Transform), (With<Ball>, With<Motion>)>,
+    mut query: Query<&mut Transform, (With<Ball>, With<Motion>)>,
+    arenas: Query<(), With<Arena>>,
 ) {
     let mut closure = |ball| -> Option<()> {
-        let (_, mut transform) = query.get_mut(ball).ok()?;
+        let mut transform = query.get_mut(ball).ok()?;
         transform.translation = Vec3::new(0.0, 0.0, -1.0);
         commands.entity(ball).remove::<Motion>();
 
@@ -587,15 +779,18 @@ fn reset_ball(
         closure(event.ball);
     }
 
-    // reset if the ball if out of range
-    for (entity, mut transform) in query.iter_mut() {
-        if transform.translation.x < -ARENA_WIDTH / 2.0
-            || transform.translation.x > ARENA_WIDTH / 2.0
-            || transform.translation.y < -ARENA_HEIGHT / 2.0
-            || transform.translation.y > ARENA_HEIGHT / 2.0
-        {
+    // reset if the ball left the arena, as of the physics rather than the interpolated transform
+    for event in contact_events.iter() {
+        if event.phase != ContactPhase::Ended {
+            continue;
+        }
+        let ball = match event.entities {
+            [arena, ball] | [ball, arena] if arenas.contains(arena) => ball,
+            _ => continue,
+        };
+        if let Ok(mut transform) = query.get_mut(ball) {
             transform.translation = Vec3::new(0.0, 0.0, -1.0);
-            commands.entity(entity).remove::<Motion>();
+            commands.entity(ball).remove::<Motion>();
         }
     }
 }
@@ -796,37 +991,6 @@ fn game_over_slow_motion(
     }
 }
 
-/// Emits [`BounceEvent`] when there the ball hits something after debouncing.
-#[allow(clippy::too_many_arguments)]
-fn ball_bounce(
-    time: Res<Time>,
-    mut timer: ResMut<Debounce>,
-    mut collision_events: EventReader<CollisionEvent>,
-    mut bounce_events: EventWriter<BounceEvent>,
-    ball_query: Query<(), With<Ball>>,
-) {
-    if timer.bounce.tick(time.delta()).finished() {
-        for event in collision_events.iter() {
-            let mut closure = |ball: Entity, other: Entity| -> Option<()> {
-                ball_query.get(ball).ok()?;
-
-                let location = event.hit.location();
-                bounce_events.send(BounceEvent {
-                    ball,
-                    other,
-                    location,
-                });
-
-                timer.bounce.reset();
-                Some(())
-            };
-
-            closure(event.entities[0], event.entities[1])
-                .or_else(|| closure(event.entities[1], event.entities[0]));
-        }
-    }
-}
-
 /// Emits [`CameraShakeEvent`] and create hit blast effects when the ball hits something (with debouncing).
 #[allow(clippy::type_complexity)]
 #[allow(clippy::too_many_arguments)]
@@ -849,7 +1013,7 @@ fn bounce_effects(
         for event in collision_events.iter() {
             let results = event.entities.map(|entity| query.get(entity).is_ok());
             if results.contains(&true) {
-                if bounce_entities.map_or(true, |entities| entities != event.entities) {
+                if bounce_entities.is_none_or(|entities| entities != event.entities) {
                     let velocities = motions.many(event.entities).map(|maybe_motion| {
                         maybe_motion.map_or(Vec2::ZERO, |motion| motion.velocity)
                     });
@@ -942,6 +1106,7 @@ fn score_effects(
 fn score_system(
     mut player_hit_events: EventReader<PlayerHitEvent>,
     mut player_miss_events: EventReader<PlayerMissEvent>,
+    mut answer_events: EventReader<AnswerEvent>,
     mut score: ResMut<Score>,
 ) {
     for _ in player_hit_events.iter() {
@@ -951,106 +1116,10 @@ fn score_system(
     for _ in player_miss_events.iter() {
         score.miss += 1;
     }
-}
 
-#[allow(clippy::too_many_arguments)]
-fn bounce_audio(
-    audio: Res<AudioChannel<BounceAudioChannel>>,
-    audios: Res<Audios>,
-    volume: Res<AudioVolume>,
-    time: Res<Time>,
-    mut timer: ResMut<Debounce>,
-    mut events: EventReader<CollisionEvent>,
-    mut bounce_entities: Local<Option<[Entity; 2]>>,
-    query: Query<(Entity, &BounceAudio)>,
-    balls: Query<(), With<Ball>>,
-    motions: Query<Option<&Motion>>,
-) {
-    let mut can_play_audio = timer.audio_bounce_long.tick(time.delta()).finished();
-    timer.audio_bounce_short.tick(time.delta());
-    timer.audio_hit.tick(time.delta());
-
-    for event in events.iter() {
-        // one of the entities must be a ball
-        let results = event.entities.map(|entity| balls.get(entity).is_ok());
-        if !results.contains(&true) {
-            continue;
+    for event in answer_events.iter() {
+        if event.correct {
+            score.answers += 1;
         }
-
-        let (entities, bounce_audio) = if let Ok(x) = query.get_many(event.entities) {
-            let (entities, bounce_audios): (Vec<_>, Vec<_>) = x.iter().cloned().unzip();
-            let bounce_audio = if bounce_audios.contains(&BounceAudio::Hit) {
-                BounceAudio::Hit
-            } else {
-                BounceAudio::Bounce
-            };
-            (entities.try_into().ok(), bounce_audio)
-        } else {
-            continue;
-        };
-
-        let (audio_source, debounce_timer) = match bounce_audio {
-            BounceAudio::Bounce => {
-                let index = fastrand::usize(..IMPACT_AUDIOS.len());
-                (
-                    audios.impact_audios[index].clone(),
-                    &timer.audio_bounce_short,
-                )
-            }
-            BounceAudio::Hit => (audios.hit_audio.clone(), &timer.audio_hit),
-        };
-
-        if entities != *bounce_entities {
-            can_play_audio = debounce_timer.finished();
-            *bounce_entities = entities;
-        }
-
-        if can_play_audio {
-            let velocities = motions
-                .many(event.entities)
-                .map(|maybe_motion| maybe_motion.map_or(Vec2::ZERO, |motion| motion.velocity));
-            let speed = (velocities[0] - velocities[1]).length();
-            if speed > MIN_BOUNCE_AUDIO_SPEED {
-                let normalized_speed = speed
-                    .intermediate(MIN_BOUNCE_AUDIO_SPEED, MAX_BOUNCE_AUDIO_SPEED)
-                    .clamp(0.0, 1.0);
-
-                let panning = event.hit.location().x / ARENA_WIDTH + 0.5;
-                let volume = volume.effects * (0.5 * normalized_speed + 0.5);
-                let playback_rate = 0.4 * fastrand::f32() + 0.8;
-                audio
-                    .play(audio_source)
-                    .with_volume(volume.into())
-                    .with_panning(panning.into())
-                    .with_playback_rate(playback_rate.into());
-
-                timer.audio_bounce_long.reset();
-                timer.audio_bounce_short.reset();
-            }
-        }
-    }
-}
-
-fn score_audio(
-    audio: Res<AudioChannel<ScoreAudioChannel>>,
-    audios: Res<Audios>,
-    volume: Res<AudioVolume>,
-    mut player_miss_events: EventReader<PlayerMissEvent>,
-    mut game_over_events: EventReader<GameOverEvent>,
-) {
-    for event in player_miss_events.iter() {
-        let panning = event.location.x / ARENA_WIDTH + 0.5;
-        audio
-            .play(audios.miss_audio.clone())
-            .with_volume(volume.effects.into())
-            .with_panning(panning.into());
-    }
-
-    for event in game_over_events.iter() {
-        let audio_source = match event {
-            GameOverEvent::Win => audios.explosion_audio.clone(),
-            GameOverEvent::Lose => audios.lose_audio.clone(),
-        };
-        audio.play(audio_source).with_volume(volume.effects.into());
     }
 }
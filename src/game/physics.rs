@@ -1,25 +1,45 @@
-use crate::{
-    constants::{PHYSICS_REST_SPEED, PHYSICS_TIME_STEP},
-    utils::*,
-    TimeScale,
+use super::field::apply_force_fields;
+use crate::{constants::*, utils::*, TimeScale};
+use bevy::{
+    prelude::*,
+    render::view::RenderLayers,
+    time::{FixedTimestep, FixedTimesteps},
+    transform::TransformSystem,
+    utils::HashSet,
 };
-use bevy::{prelude::*, render::view::RenderLayers, time::FixedTimestep};
 
 pub struct PhysicsPlugin;
 
 impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
-        let systems = SystemSet::new()
-            .with_run_criteria(FixedTimestep::step(PHYSICS_TIME_STEP as f64))
-            .with_system(init_motion)
-            .with_system(movement)
-            .with_system(collision.after(init_motion).after(movement));
+        let systems = physics_systems().label(PhysicsSystems).with_run_criteria(
+            FixedTimestep::step(PHYSICS_TIME_STEP as f64).with_label(PHYSICS_TIME_STEP_LABEL),
+        );
 
         app.add_event::<CollisionEvent>()
-            .add_system_set_to_stage(CoreStage::PostUpdate, systems);
+            .add_event::<ContactEvent>()
+            .add_system_to_stage(CoreStage::PostUpdate, init_motion.before(PhysicsSystems))
+            .add_system_set_to_stage(CoreStage::PostUpdate, systems)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                interpolate_motion
+                    .after(PhysicsSystems)
+                    .before(TransformSystem::TransformPropagate),
+            );
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemLabel)]
+pub struct PhysicsSystems;
+
+/// Systems of one physics step, without the fixed time step they run at.
+pub fn physics_systems() -> SystemSet {
+    SystemSet::new()
+        .with_system(apply_force_fields.before(movement))
+        .with_system(movement)
+        .with_system(collision.after(movement))
+}
+
 #[derive(Clone, Component)]
 pub struct PhysicsLayers {
     pub collision: RenderLayers,
@@ -29,7 +49,7 @@ pub struct PhysicsLayers {
 
 impl PhysicsLayers {
     pub const BALL: Self = Self {
-        collision: RenderLayers::layer(1).with(2),
+        collision: RenderLayers::layer(1).with(2).with(5),
         bounciness: RenderLayers::layer(1).with(2),
         friction: RenderLayers::layer(1).with(2),
     };
@@ -48,18 +68,27 @@ impl PhysicsLayers {
         bounciness: RenderLayers::none(),
         friction: RenderLayers::none(),
     };
+    pub const ARENA: Self = Self {
+        collision: RenderLayers::layer(5),
+        bounciness: RenderLayers::none(),
+        friction: RenderLayers::none(),
+    };
 }
 
 #[derive(Component)]
 pub struct RigidBody {
-    pub size: Vec2,
+    pub shape: Shape,
     pub inverted_mass: f32,
     pub bounciness: f32,
     pub friction: f32,
+    /// Sensors only detect overlaps, reported as [`ContactEvent`]s, and are passed through.
+    pub sensor: bool,
+    /// Direction other bodies may pass through this one in; from the other side it is solid.
+    pub pass_through: Option<Vec2>,
 }
 
 impl RigidBody {
-    pub fn new(size: Vec2, mass: f32, bounciness: f32, friction: f32) -> Self {
+    pub fn new(shape: Shape, mass: f32, bounciness: f32, friction: f32) -> Self {
         let inverted_mass = if mass < f32::EPSILON {
             0.0
         } else {
@@ -67,22 +96,70 @@ impl RigidBody {
         };
 
         Self {
-            size,
+            shape,
             inverted_mass,
             bounciness,
             friction,
+            sensor: false,
+            pass_through: None,
+        }
+    }
+
+    /// Makes the body a sensor.
+    pub fn sensor(self) -> Self {
+        Self {
+            sensor: true,
+            ..self
+        }
+    }
+
+    /// Makes the body one-way, letting bodies through in the given direction.
+    pub fn one_way(self, direction: Vec2) -> Self {
+        Self {
+            pass_through: Some(direction.normalize_or_zero()),
+            ..self
         }
     }
 
+    /// Whether a contact with another body is resolved, `normal` pointing from this body to the other
+    /// and `velocity` that of the other relative to this one. One-way bodies only block those
+    /// on their far side heading back against the pass-through direction.
+    pub fn blocks(&self, normal: Vec2, velocity: Vec2) -> bool {
+        self.pass_through
+            .is_none_or(|direction| normal.dot(direction) > 0.0 && velocity.dot(direction) < 0.0)
+    }
+
     pub fn mass(&self) -> f32 {
         self.inverted_mass.recip()
     }
 }
 
+/// Physics state of a moving body, kept apart from its [`Transform`],
+/// which is only interpolated from it for rendering.
 #[derive(Default, Component)]
 pub struct Motion {
     pub velocity: Vec2,
+    /// Position after the last physics step.
     pub translation: Vec3,
+    /// Position before the last physics step.
+    pub previous_translation: Vec3,
+    /// Spin in radians per second, counterclockwise.
+    pub angular_velocity: f32,
+}
+
+/// Whether a [`ContactEvent`] is for bodies that just touched, still touch, or stopped touching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactPhase {
+    Started,
+    Persisting,
+    Ended,
+}
+
+/// Sent every physics step for each pair of bodies touching, or touching in the step before.
+/// Unlike [`CollisionEvent`] it includes sensors, and contacts that didn't need resolving.
+pub struct ContactEvent {
+    pub entities: [Entity; 2],
+    pub phase: ContactPhase,
 }
 
 pub struct CollisionEvent {
@@ -96,145 +173,398 @@ pub struct CollisionEvent {
 fn init_motion(mut query: Query<(&Transform, &mut Motion), Added<Motion>>) {
     for (transform, mut motion) in query.iter_mut() {
         motion.translation = transform.translation;
+        motion.previous_translation = transform.translation;
     }
 }
 
-fn movement(time_scale: Res<TimeScale>, mut query: Query<(&mut Motion, &mut Transform)>) {
-    for (mut motion, mut transform) in query.iter_mut() {
-        motion.translation = transform.translation;
+fn movement(time_scale: Res<TimeScale>, mut query: Query<&mut Motion>) {
+    for mut motion in query.iter_mut() {
+        motion.previous_translation = motion.translation;
 
         let delta_time = PHYSICS_TIME_STEP * time_scale.0;
-        transform.translation += motion.velocity.extend(0.0) * delta_time;
+        let delta = motion.velocity.extend(0.0) * delta_time;
+        motion.translation += delta;
+    }
+}
+
+/// Renders moving bodies between the last two physics steps,
+/// as far as the time left over from the fixed time step goes towards the next one.
+fn interpolate_motion(
+    fixed_timesteps: Res<FixedTimesteps>,
+    mut query: Query<(&Motion, &mut Transform)>,
+) {
+    let factor = fixed_timesteps
+        .get(PHYSICS_TIME_STEP_LABEL)
+        .map_or(1.0, |state| state.overstep_percentage() as f32)
+        .min(1.0);
+
+    for (motion, mut transform) in query.iter_mut() {
+        let translation = motion.previous_translation.lerp(motion.translation, factor);
+        transform.translation = translation.truncate().extend(transform.translation.z);
+    }
+}
+
+/// A body as the solver sees it; those without [`Motion`] never move.
+#[derive(Clone, Copy)]
+pub struct SolverBody {
+    inverted_mass: f32,
+    /// Radius of round bodies, which friction spins.
+    radius: Option<f32>,
+    pub velocity: Vec2,
+    pub angular_velocity: f32,
+    /// Displacement from position correction, and from rewinding to the time of impact.
+    pub correction: Vec2,
+}
+
+impl SolverBody {
+    pub fn moving(rigid_body: &RigidBody, velocity: Vec2, angular_velocity: f32) -> Self {
+        Self {
+            inverted_mass: rigid_body.inverted_mass,
+            radius: match rigid_body.shape {
+                Shape::Circle(radius) => Some(radius),
+                _ => None,
+            },
+            velocity,
+            angular_velocity,
+            correction: Vec2::ZERO,
+        }
+    }
+
+    pub fn fixed(rigid_body: &RigidBody) -> Self {
+        Self {
+            inverted_mass: 0.0,
+            ..Self::moving(rigid_body, Vec2::ZERO, 0.0)
+        }
+    }
+
+    /// Speed of the rim of a round body across a contact normal.
+    fn rim_speed(&self) -> f32 {
+        self.radius
+            .map_or(0.0, |radius| self.angular_velocity * radius)
+    }
+
+    /// Inverted mass against a tangential impulse at the rim, which also spins round bodies.
+    fn tangent_inverted_mass(&self) -> f32 {
+        match self.radius {
+            Some(_) => 3.0 * self.inverted_mass,
+            None => self.inverted_mass,
+        }
+    }
+}
+
+/// A contact gathered in a physics step, with the impulses accumulated on it by the solver.
+pub struct Contact {
+    bodies: [usize; 2],
+    entities: [Entity; 2],
+    /// Points from the second body towards the first.
+    normal: Vec2,
+    /// Penetration depth, as of no correction.
+    depth: f32,
+    bounciness: f32,
+    friction: f32,
+    /// Speed the bodies should end up separating at.
+    separating_speed: f32,
+    normal_impulse: f32,
+    tangent_impulse: f32,
+    hit: Hit,
+}
+
+impl Contact {
+    /// Contact of the `hit` of the bodies at indices `a` and `b`; `None` if it needs no resolving,
+    /// as one lets the other through or neither moves. Only new contacts bounce, not `persisting` ones.
+    pub fn new(
+        [a, b]: [usize; 2],
+        entities: [Entity; 2],
+        [rb1, rb2]: [&RigidBody; 2],
+        layers: [&PhysicsLayers; 2],
+        bodies: &[SolverBody],
+        hit: Hit,
+        persisting: bool,
+    ) -> Option<Self> {
+        let normal = hit.normal();
+        let (v1, v2) = (bodies[a].velocity, bodies[b].velocity);
+        if !rb1.blocks(-normal, v2 - v1) || !rb2.blocks(normal, v1 - v2) {
+            return None;
+        }
+        if bodies[a].inverted_mass + bodies[b].inverted_mass < f32::EPSILON {
+            return None;
+        }
+
+        let (bounciness, friction) = contact_coefficients([rb1, rb2], layers);
+        let normal_speed = (v2 - v1).dot(normal);
+        // persisting contacts are resting, or wedged between other bodies
+        let separating_speed = if normal_speed < PHYSICS_REST_SPEED || persisting {
+            0.0
+        } else {
+            bounciness * normal_speed
+        };
+        let depth = match &hit {
+            Hit::Penetration(x) => x.depth,
+            Hit::Cast(_) => 0.0,
+        };
+
+        Some(Self {
+            bodies: [a, b],
+            entities,
+            normal,
+            depth,
+            bounciness,
+            friction,
+            separating_speed,
+            normal_impulse: 0.0,
+            tangent_impulse: 0.0,
+            hit,
+        })
+    }
+
+    /// Speed of the second body towards the first along the normal, and of its surface across it.
+    fn relative_speeds(&self, bodies: &[SolverBody]) -> (f32, f32) {
+        let [a, b] = self.bodies;
+        let velocity = bodies[b].velocity - bodies[a].velocity;
+        // the rims spin the opposite ways at the contact, as it is on opposite sides of the two
+        let rim_speed = bodies[a].rim_speed() + bodies[b].rim_speed();
+        (
+            velocity.dot(self.normal),
+            velocity.dot(self.normal.perp()) + rim_speed,
+        )
+    }
+
+    /// Penetration depth left after the bodies have been corrected.
+    fn current_depth(&self, bodies: &[SolverBody]) -> f32 {
+        let [a, b] = self.bodies;
+        self.depth - (bodies[a].correction - bodies[b].correction).dot(self.normal)
+    }
+
+    /// Applies an impulse to the first body, and the opposite one to the second.
+    fn apply_impulse(&self, bodies: &mut [SolverBody], normal_impulse: f32, tangent_impulse: f32) {
+        let impulse = normal_impulse * self.normal + tangent_impulse * self.normal.perp();
+        for (index, sign) in [(self.bodies[0], 1.0), (self.bodies[1], -1.0)] {
+            let body = &mut bodies[index];
+            body.velocity += sign * impulse * body.inverted_mass;
+
+            // the tangential impulse at the rim spins round bodies, taken as solid disks
+            if let Some(radius) = body.radius {
+                body.angular_velocity -= 2.0 * tangent_impulse * body.inverted_mass / radius;
+            }
+        }
+    }
+
+    fn delta_velocities(&self, bodies: &[SolverBody]) -> [Vec2; 2] {
+        let impulse = self.normal_impulse * self.normal + self.tangent_impulse * self.normal.perp();
+        let [a, b] = self.bodies;
+        [
+            impulse * bodies[a].inverted_mass,
+            -impulse * bodies[b].inverted_mass,
+        ]
+    }
+}
+
+/// Resolves the contacts of a step between `bodies`, whose `colliders` moved over it:
+/// moving bodies go back to where they first hit something, then the solver pushes them apart.
+pub fn resolve(contacts: &mut [Contact], bodies: &mut [SolverBody], colliders: &[Collider]) {
+    // moving bodies that hit something during the step go back to their earliest time of impact
+    let mut impact_times = vec![1.0_f32; bodies.len()];
+    for contact in contacts.iter() {
+        if let Hit::Cast(x) = &contact.hit {
+            if x.near_time > 0.0 {
+                for index in contact.bodies {
+                    impact_times[index] = impact_times[index].min(x.near_time);
+                }
+            }
+        }
+    }
+    for (index, body) in bodies.iter_mut().enumerate() {
+        if body.inverted_mass > 0.0 {
+            body.correction = (colliders[index].previous_position - colliders[index].position)
+                * (1.0 - impact_times[index]);
+        }
+    }
+    for contact in contacts.iter_mut() {
+        if let Hit::Cast(_) = contact.hit {
+            // just touching where the bodies went back to
+            let [a, b] = contact.bodies;
+            contact.depth = (bodies[a].correction - bodies[b].correction).dot(contact.normal);
+        }
+    }
+
+    // sequential impulses: every contact is solved in turn, with impulses accumulated over iterations
+    for _ in 0..PHYSICS_SOLVER_ITERATIONS {
+        for contact in contacts.iter_mut() {
+            let [a, b] = contact.bodies;
+            let mass = (bodies[a].inverted_mass + bodies[b].inverted_mass).recip();
+            let tangent_mass =
+                (bodies[a].tangent_inverted_mass() + bodies[b].tangent_inverted_mass()).recip();
+            let (normal_speed, tangent_speed) = contact.relative_speeds(bodies);
+
+            let normal_impulse = (contact.normal_impulse
+                + mass * (normal_speed + contact.separating_speed))
+                .max(0.0);
+            let max_friction = contact.friction * normal_impulse;
+            let tangent_impulse = (contact.tangent_impulse + tangent_mass * tangent_speed)
+                .clamp(-max_friction, max_friction);
+
+            contact.apply_impulse(
+                bodies,
+                normal_impulse - contact.normal_impulse,
+                tangent_impulse - contact.tangent_impulse,
+            );
+            contact.normal_impulse = normal_impulse;
+            contact.tangent_impulse = tangent_impulse;
+        }
+    }
+
+    // position correction: push penetrating bodies apart, lighter ones further
+    for _ in 0..PHYSICS_POSITION_ITERATIONS {
+        for contact in contacts.iter() {
+            let [a, b] = contact.bodies;
+            let depth = contact.current_depth(bodies) - PHYSICS_PENETRATION_SLOP;
+            if depth <= 0.0 {
+                continue;
+            }
+
+            let [im1, im2] = [bodies[a].inverted_mass, bodies[b].inverted_mass];
+            let correction = PHYSICS_POSITION_CORRECTION * depth / (im1 + im2) * contact.normal;
+            bodies[a].correction += correction * im1;
+            bodies[b].correction -= correction * im2;
+        }
     }
 }
 
 #[allow(clippy::type_complexity)]
 fn collision(
-    time_scale: Res<TimeScale>,
     mut query: Query<(
         Entity,
         &RigidBody,
-        &mut Transform,
+        &Transform,
         Option<&mut Motion>,
         &PhysicsLayers,
     )>,
     mut events: EventWriter<CollisionEvent>,
+    mut contact_events: EventWriter<ContactEvent>,
+    mut touching_before: Local<HashSet<[Entity; 2]>>,
 ) {
-    let delta_time = PHYSICS_TIME_STEP * time_scale.0;
-    let mut combinations = query.iter_combinations_mut();
-    while let Some([(e1, rb1, t1, m1, pl1), (e2, rb2, t2, m2, pl2)]) = combinations.fetch_next() {
-        if !pl1.collision.intersects(&pl2.collision) {
-            continue;
-        }
+    let items: Vec<_> = query.iter().collect();
 
-        let (p1, v1) = match &m1 {
-            Some(motion) => (motion.translation, motion.velocity),
-            None => (t1.translation, Vec2::ZERO),
-        };
-        let (p2, v2) = match &m2 {
-            Some(motion) => (motion.translation, motion.velocity),
-            None => (t2.translation, Vec2::ZERO),
+    // broad phase: only pairs that may collide, and whose swept bounds overlap
+    let colliders: Vec<_> = items
+        .iter()
+        .map(|(_, rigid_body, transform, motion, _)| collider(rigid_body, transform, *motion))
+        .collect();
+    let bounds: Vec<_> = colliders.iter().map(Collider::swept_bounds).collect();
+    let candidates = overlapping_pairs(&bounds)
+        .into_iter()
+        .filter(|&[a, b]| items[a].4.collision.intersects(&items[b].4.collision));
+
+    let mut bodies: Vec<_> = items
+        .iter()
+        .map(|(_, rigid_body, _, motion, _)| match motion {
+            Some(motion) => {
+                SolverBody::moving(rigid_body, motion.velocity, motion.angular_velocity)
+            }
+            None => SolverBody::fixed(rigid_body),
+        })
+        .collect();
+
+    // narrow phase: gather every contact before resolving any, so they don't depend on the order of pairs
+    let mut touching = HashSet::default();
+    let mut contacts = vec![];
+    for [a, b] in candidates {
+        let hit = match collide(&colliders[a], &colliders[b]) {
+            Some(hit) => hit,
+            None => continue,
         };
+        let (e1, rb1, _, _, pl1) = items[a];
+        let (e2, rb2, _, _, pl2) = items[b];
 
-        if let Some(hit) = collide(
-            &Collider {
-                previous_position: p1.truncate(),
-                position: t1.translation.truncate(),
-                size: rb1.size,
-            },
-            &Collider {
-                previous_position: p2.truncate(),
-                position: t2.translation.truncate(),
-                size: rb2.size,
-            },
-        ) {
-            let bounciness = if pl1.bounciness.intersects(&pl2.bounciness) {
-                (rb1.bounciness * rb2.bounciness).sqrt()
-            } else {
-                0.0
-            };
-            let friction = if pl1.friction.intersects(&pl2.friction) {
-                (rb1.friction * rb2.friction).sqrt()
-            } else {
-                0.0
-            };
-            let impulse = (rb1.inverted_mass + rb2.inverted_mass).recip();
-            let normal = hit.normal();
-
-            let resolve = |rigid_body: &RigidBody,
-                           mut motion: Mut<Motion>,
-                           mut transform: Mut<Transform>,
-                           velocity: Vec2,
-                           normal: Vec2|
-             -> Option<Vec2> {
-                let normal_speed = velocity.dot(normal);
-
-                // do not process if objects are moving apart
-                if normal_speed < 0.0 {
-                    return None;
-                }
+        let mut entities = [e1, e2];
+        entities.sort();
+        touching.insert(entities);
 
-                let tan = (velocity - normal_speed * normal).normalize_or_zero();
-                let tan_speed = velocity.dot(tan);
-
-                let bounciness = if normal_speed < PHYSICS_REST_SPEED {
-                    0.0
-                } else {
-                    bounciness
-                };
-
-                let normal_impulse = (1.0 + bounciness) * impulse * normal_speed;
-                let tan_impulse = (impulse * tan_speed).min(friction * normal_impulse);
-
-                let normal_delta = normal_impulse * rigid_body.inverted_mass;
-                let tan_delta = tan_impulse * rigid_body.inverted_mass;
-                let delta_velocity = normal_delta * normal + tan_delta * tan;
-                motion.velocity += delta_velocity;
-
-                // translation correction
-                match &hit {
-                    Hit::Penetration(x) => {
-                        let delta = (motion.translation - transform.translation)
-                            .truncate()
-                            .dot(normal);
-                        let depth = x.depth.abs();
-
-                        // compensate penetration based on masses
-                        let correction = depth * impulse * rigid_body.inverted_mass;
-                        // compensate normal impulse to be applied in the next physics update
-                        let debounce = (1.0 - bounciness) * normal_delta * delta_time;
-                        let normal_delta = delta + correction - debounce;
-                        transform.translation += normal_delta * normal.extend(0.0);
-                    }
-                    Hit::Cast(x) => {
-                        if x.near_time > 0.0 {
-                            transform.translation =
-                                motion.translation.lerp(transform.translation, x.near_time);
-                        }
-                    }
-                };
-
-                Some(delta_velocity)
-            };
-
-            let mut delta_velocities: [Vec2; 2] = Default::default();
-            if let Some(motion) = m1 {
-                delta_velocities[0] = resolve(rb1, motion, t1, v2 - v1, normal).unwrap_or_default();
-            }
-            if let Some(motion) = m2 {
-                delta_velocities[1] =
-                    resolve(rb2, motion, t2, v1 - v2, -normal).unwrap_or_default();
-            }
+        if rb1.sensor || rb2.sensor {
+            continue;
+        }
+
+        let persisting = touching_before.contains(&entities);
+        contacts.extend(Contact::new(
+            [a, b],
+            entities,
+            [rb1, rb2],
+            [pl1, pl2],
+            &bodies,
+            hit,
+            persisting,
+        ));
+    }
+
+    resolve(&mut contacts, &mut bodies, &colliders);
 
-            let mut entities = [e1, e2];
-            entities.sort();
-            events.send(CollisionEvent {
-                entities,
-                delta_velocities,
-                bounciness,
-                friction,
-                hit,
-            });
+    let entities: Vec<_> = items.iter().map(|(entity, ..)| *entity).collect();
+    for (entity, body) in entities.into_iter().zip(&bodies) {
+        if let Ok((_, _, _, Some(mut motion), _)) = query.get_mut(entity) {
+            motion.velocity = body.velocity;
+            motion.angular_velocity = body.angular_velocity;
+            motion.translation += body.correction.extend(0.0);
         }
     }
+
+    for contact in contacts {
+        events.send(CollisionEvent {
+            entities: contact.entities,
+            delta_velocities: contact.delta_velocities(&bodies),
+            bounciness: contact.bounciness,
+            friction: contact.friction,
+            hit: contact.hit,
+        });
+    }
+
+    for &entities in &touching {
+        let phase = if touching_before.contains(&entities) {
+            ContactPhase::Persisting
+        } else {
+            ContactPhase::Started
+        };
+        contact_events.send(ContactEvent { entities, phase });
+    }
+    for &entities in touching_before.difference(&touching) {
+        contact_events.send(ContactEvent {
+            entities,
+            phase: ContactPhase::Ended,
+        });
+    }
+    *touching_before = touching;
+}
+
+/// Bounciness and friction of a contact between two bodies, none where their layers don't share them.
+pub fn contact_coefficients(
+    [rb1, rb2]: [&RigidBody; 2],
+    [pl1, pl2]: [&PhysicsLayers; 2],
+) -> (f32, f32) {
+    let bounciness = if pl1.bounciness.intersects(&pl2.bounciness) {
+        (rb1.bounciness * rb2.bounciness).sqrt()
+    } else {
+        0.0
+    };
+    let friction = if pl1.friction.intersects(&pl2.friction) {
+        (rb1.friction * rb2.friction).sqrt()
+    } else {
+        0.0
+    };
+    (bounciness, friction)
+}
+
+/// Collider of a body over the last physics step; bodies without [`Motion`] stay where their transform is.
+pub fn collider(
+    rigid_body: &RigidBody,
+    transform: &Transform,
+    motion: Option<&Motion>,
+) -> Collider {
+    let (previous_position, position) = match motion {
+        Some(motion) => (motion.previous_translation, motion.translation),
+        None => (transform.translation, transform.translation),
+    };
+    Collider {
+        previous_position: previous_position.truncate(),
+        position: position.truncate(),
+        shape: rigid_body.shape,
+    }
 }
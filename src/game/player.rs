@@ -1,13 +1,12 @@
 use super::{
     ball::{Ball, Point, Trajectory},
+    base::PlayerBase,
     enemy::Controller,
+    layout::TypedEvent,
     physics::{CollisionEvent, Motion},
 };
 use crate::{constants::*, utils::Damp, TimeScale};
-use bevy::{
-    input::{keyboard::KeyboardInput, mouse::MouseMotion},
-    prelude::*,
-};
+use bevy::{input::mouse::MouseMotion, prelude::*};
 use std::{ops::Add, time::Duration};
 
 #[derive(Component)]
@@ -71,10 +70,9 @@ pub struct ConfirmEvent {
 }
 
 pub fn handle_input(
-    mut events: EventReader<ReceivedCharacter>,
+    mut events: EventReader<TypedEvent>,
     mut confirm_event: EventWriter<ConfirmEvent>,
     mut edit_text: Query<&mut Text, With<InputCodes>>,
-    query: Query<(Entity, &Player, With<LocationTarget>)>,
 ) {
     if edit_text.is_empty() {
         return;
@@ -82,11 +80,6 @@ pub fn handle_input(
 
     for event in events.iter() {
         if event.char == ' ' {
-            println!("send confirm event");
-            if !query.is_empty() {
-                println!("already have location target");
-                return;
-            }
             confirm_event.send(ConfirmEvent {
                 codes: edit_text.single_mut().sections[0].value.clone(),
             });
@@ -97,34 +90,10 @@ pub fn handle_input(
     }
 }
 
-#[derive(Component)]
-pub struct LocationTarget {
-    location: Vec3,
-}
-
-pub fn add_location_target(
-    mut command: Commands,
-    events: EventReader<ConfirmEvent>,
-    query: Query<(Entity, &Player, Without<LocationTarget>)>,
-) {
-    if events.len() > 0 {
-        println!("confirm event trigger");
-
-        for (entity, _player, _) in query.iter() {
-            command.entity(entity).insert(LocationTarget {
-                location: Vec3::new(0.0, 0.0, 0.0),
-            });
-        }
-    }
-}
-
 pub fn move_player(
     time: Res<Time>,
     time_scale: Res<TimeScale>,
     mut mouse_motion_events: EventReader<MouseMotion>,
-    keyboard_input: Res<Input<KeyCode>>,
-    mut text_querys: ParamSet<(Query<&mut Text, With<Ball>>, Query<&mut Text>)>,
-    player_query: Query<&Children, With<Player>>,
     mut query: Query<(&Player, &Controller, &mut MotionOverride, &mut Motion)>,
 ) {
     let delta = mouse_motion_events
@@ -165,6 +134,7 @@ pub fn assist_player(
         (With<Player>, Without<Ball>),
     >,
     ball_query: Query<(&Motion, &Trajectory), With<Ball>>,
+    bases: Query<(), With<PlayerBase>>,
 ) {
     for (transform, assist, mut controller, _) in query.iter_mut() {
         controller.velocity = Vec2::ZERO;
@@ -180,7 +150,7 @@ pub fn assist_player(
                 // very dangerous, try to assist the player
                 let delta_seconds = time.elapsed_seconds() - trajectory.start_time;
                 if let Some(candidate) = trajectory
-                    .points
+                    .in_play(&bases)
                     .iter()
                     .filter(|point| point.position.y < 0.0)
                     .filter(|point| point.position.y > -ARENA_HEIGHT / 2.0 + 16.0)
@@ -243,3 +213,58 @@ pub fn assist_player(
         closure(event.entities[1], event.entities[0]);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            fuzzy::FuzzyPinyin,
+            glyph::{check_answer, AnswerEvent, Glyph},
+            rime::RimeScheme,
+            scheme::Scheme,
+            score_system, PlayerHitEvent, PlayerMissEvent, Score,
+        },
+        *,
+    };
+
+    fn typed(text: &str) -> impl Iterator<Item = TypedEvent> + '_ {
+        text.chars().map(|char| TypedEvent {
+            char,
+            timestamp: 0.0,
+        })
+    }
+
+    #[test]
+    fn every_confirm_in_a_round_is_answered() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<Score>()
+            .init_resource::<Scheme>()
+            .init_resource::<RimeScheme>()
+            .init_resource::<FuzzyPinyin>()
+            .add_event::<TypedEvent>()
+            .add_event::<ConfirmEvent>()
+            .add_event::<AnswerEvent>()
+            .add_event::<PlayerHitEvent>()
+            .add_event::<PlayerMissEvent>()
+            .add_system(handle_input)
+            .add_system(check_answer.after(handle_input))
+            .add_system(score_system.after(check_answer));
+
+        app.world
+            .spawn((Text::from_section("", default()), InputCodes));
+        app.world.spawn((Player::default(), Motion::default()));
+        for (character, reading) in [("中", "zhong1"), ("文", "wen2"), ("好", "hao3")] {
+            app.world
+                .spawn((Glyph::from_reading(character, reading), Motion::default()));
+        }
+
+        // a wrong answer in between doesn't stop the ones after it
+        for text in ["zhong ", "xyz wen ", "hao "] {
+            app.world.send_event_batch(typed(text));
+            app.update();
+        }
+
+        assert_eq!(app.world.resource::<Score>().answers, 3);
+    }
+}
@@ -10,23 +10,23 @@ impl Plugin for PracticePlugin {
                     .with_system(enter_practice)
                     .with_system(make_arena)
                     .with_system(make_ui)
+                    .with_system(make_keyboard)
                     .with_system(make_player)
-                    .with_system(make_ball),
+                    .with_system(make_ball)
+                    .with_system(make_sentence),
             )
             .add_system_set(
-                SystemSet::on_update(AppState::Practice)
+                endless_systems()
+                    .with_run_criteria(State::on_update(AppState::Practice))
+                    .with_system(change_slits.after(player_ball_infinite))
                     .with_system(escape_system)
                     .with_system(progress_system)
-                    .with_system(change_slits)
-                    .with_system(validate_slit_block)
-                    .with_system(reset_ball)
-                    .with_system(player_hit)
-                    .with_system(player_miss)
-                    .with_system(recover_enemy_health)
-                    .with_system(player_ball_infinite),
+                    .with_system(fade_slit_block),
             )
             .add_system_set(
-                SystemSet::on_exit(AppState::Practice).with_system(cleanup_system::<Cleanup>),
+                SystemSet::on_exit(AppState::Practice)
+                    .with_system(cleanup_system::<Cleanup>)
+                    .with_system(clear_glyph_queue),
             )
             .add_system_set(
                 SystemSet::on_enter(PracticeState::Slits).with_system(make_slit_blocks),
@@ -43,31 +43,41 @@ pub enum PracticeState {
 #[allow(clippy::too_many_arguments)]
 fn enter_practice(
     mut practice_state: ResMut<State<PracticeState>>,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    volume: Res<AudioVolume>,
     mut music_track: ResMut<MusicTrack>,
     mut time_scale: ResMut<TimeScale>,
+    mut review: ResMut<Review>,
+    mut finger_stats: ResMut<FingerStats>,
+    mut last_keystroke: ResMut<LastKeystroke>,
     mut heal_events: EventWriter<HealEvent>,
 ) {
     let _ = practice_state.set(PracticeState::Plain);
 
+    review.mistakes.clear();
+    *finger_stats = FingerStats::default();
+    last_keystroke.0 = None;
+
     time_scale.reset();
 
     heal_events.send(HealEvent(Heal::default()));
 
     if music_track.0 != GAME_MUSIC {
-        audio.stop();
-        audio.set_volume(volume.music.into());
-        audio.set_playback_rate(1.2);
-        audio.play(asset_server.load(GAME_MUSIC)).looped();
-
         music_track.0 = GAME_MUSIC;
     }
 }
 
 /// Triggers a full recovery of enemy base health after beating it.
-fn recover_enemy_health(
+/// Balls of practice and sprints, which never run out, shared with [`simulate`].
+pub fn endless_systems() -> SystemSet {
+    SystemSet::new()
+        .after(score_system)
+        .with_system(reset_ball)
+        .with_system(player_hit.after(reset_ball))
+        .with_system(player_miss.after(player_hit))
+        .with_system(recover_enemy_health.after(player_miss))
+        .with_system(player_ball_infinite.after(recover_enemy_health))
+}
+
+pub fn recover_enemy_health(
     time: Res<Time>,
     mut game_over_events: EventReader<GameOverEvent>,
     mut game_over: Local<GameOver>,
@@ -95,7 +105,7 @@ fn recover_enemy_health(
 }
 
 /// Make the player's ball count infinite.
-fn player_ball_infinite(mut query: Query<&mut PlayerBase>) {
+pub fn player_ball_infinite(mut query: Query<&mut PlayerBase>) {
     if let Ok(mut base) = query.get_single_mut() {
         base.ball_count = 99;
     }
@@ -124,6 +134,17 @@ fn progress_system(
     }
 }
 
+/// Slit blocks let balls fall through from the top, and bounce them off the bottom.
+pub fn slit_block_body() -> RigidBody {
+    RigidBody::new(
+        Shape::Aabb(Vec2::new(SLIT_BLOCK_WIDTH, SLIT_BLOCK_HEIGHT)),
+        0.0,
+        1.0,
+        0.0,
+    )
+    .one_way(Vec2::NEG_Y)
+}
+
 fn make_slit_blocks(mut commands: Commands, materials: Res<Materials>, mut slits: ResMut<Slits>) {
     let slits_index = slits.count / 2;
     slits.state = SlitState::Stand(slits_index);
@@ -149,12 +170,7 @@ fn make_slit_blocks(mut commands: Commands, materials: Res<Materials>, mut slits
                     },
                     ..Default::default()
                 },
-                RigidBody::new(
-                    Vec2::new(SLIT_BLOCK_WIDTH, SLIT_BLOCK_HEIGHT),
-                    0.0,
-                    1.0,
-                    0.0,
-                ),
+                slit_block_body(),
                 PhysicsLayers::BOUNDARY,
                 BounceAudio::Bounce,
                 slit_block,
@@ -176,7 +192,7 @@ fn make_slit_blocks(mut commands: Commands, materials: Res<Materials>, mut slits
     }
 }
 
-fn change_slits(mut slits: ResMut<Slits>, mut player_hit_events: EventReader<PlayerHitEvent>) {
+pub fn change_slits(mut slits: ResMut<Slits>, mut player_hit_events: EventReader<PlayerHitEvent>) {
     for _ in player_hit_events.iter() {
         let previous = match &slits.state {
             SlitState::Stand(index) => *index,
@@ -195,21 +211,18 @@ fn change_slits(mut slits: ResMut<Slits>, mut player_hit_events: EventReader<Pla
     }
 }
 
-/// Temporary disables collision between ball and slit blocks when the ball is on top and moving down.
-#[allow(clippy::type_complexity)]
-fn validate_slit_block(
-    mut query: Query<(&Transform, &mut PhysicsLayers, &mut Sprite), With<SlitBlock>>,
-    balls: Query<(&Transform, &Motion), (With<Ball>, Without<SlitBlock>)>,
+/// Fades slit blocks while a ball on top of them is moving down, and so can fall through them.
+fn fade_slit_block(
+    mut query: Query<(&Transform, &mut Sprite), With<SlitBlock>>,
+    balls: Query<&Motion, With<Ball>>,
 ) {
-    for (slit_block_transform, mut physics_layers, mut sprite) in query.iter_mut() {
-        *physics_layers = PhysicsLayers::BOUNDARY;
+    for (slit_block_transform, mut sprite) in query.iter_mut() {
         sprite.color = PADDLE_COLOR;
 
-        for (ball_transform, motion) in balls.iter() {
-            if ball_transform.translation.y + BALL_SIZE > slit_block_transform.translation.y
+        for motion in balls.iter() {
+            if motion.translation.y + BALL_SIZE > slit_block_transform.translation.y
                 && motion.velocity.y < 0.0
             {
-                *physics_layers = PhysicsLayers::SEPARATE;
                 sprite.color.set_a(0.2);
             }
         }
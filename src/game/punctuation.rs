@@ -0,0 +1,132 @@
+use super::glyph::Glyph;
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// Full-width punctuation and the key a standard pinyin IME types it with.
+/// Both quotes share a key, the IME alternates between opening and closing them.
+const PUNCTUATION: [(&str, &str); 11] = [
+    ("。", "."),
+    ("，", ","),
+    ("、", "\\"),
+    ("；", ";"),
+    ("：", ":"),
+    ("？", "?"),
+    ("！", "!"),
+    ("“", "\""),
+    ("”", "\""),
+    ("《", "<"),
+    ("》", ">"),
+];
+
+/// Whether punctuation is mixed into rounds taken from the word list.
+#[derive(Default, Resource)]
+pub struct PunctuationMix {
+    pub enabled: bool,
+}
+
+/// Whether a typed character is the key of a punctuation mark.
+pub fn is_punctuation_key(char: char) -> bool {
+    PUNCTUATION
+        .iter()
+        .any(|(_, key)| key.chars().eq(std::iter::once(char)))
+}
+
+/// Every punctuation mark once, with the quotes kept together so they open before they close.
+pub fn punctuation_glyphs() -> Vec<Glyph> {
+    PUNCTUATION
+        .iter()
+        .map(|(character, key)| Glyph::new(character, "", &[key]))
+        .collect()
+}
+
+/// Inserts a punctuation mark after every [`PUNCTUATION_MIX_INTERVAL`] glyphs.
+/// Quotes are inserted as a pair around the glyphs in between, as they'd appear in a passage.
+pub fn mix_punctuation(glyphs: &[Glyph]) -> Vec<Glyph> {
+    let mut marks = punctuation_glyphs().into_iter().cycle();
+    let mut mixed = vec![];
+    let mut closing = None;
+
+    for chunk in glyphs.chunks(PUNCTUATION_MIX_INTERVAL) {
+        mixed.extend_from_slice(chunk);
+        if let Some(mark) = closing.take() {
+            mixed.push(mark);
+            continue;
+        }
+
+        let mark = marks.next().unwrap();
+        if mark.character == "“" {
+            // open here, close after the next chunk
+            closing = marks.next();
+        }
+        mixed.push(mark);
+    }
+    mixed.extend(closing);
+
+    mixed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn characters(glyphs: &[Glyph]) -> Vec<&str> {
+        glyphs
+            .iter()
+            .map(|glyph| glyph.character.as_str())
+            .collect()
+    }
+
+    fn words(count: usize) -> Vec<Glyph> {
+        vec![Glyph::new("字", "zi4", &["zi"]); count]
+    }
+
+    #[test]
+    fn marks_follow_every_interval() {
+        let mixed = mix_punctuation(&words(PUNCTUATION_MIX_INTERVAL * 3));
+        let marks = mixed
+            .iter()
+            .enumerate()
+            .filter(|(_, glyph)| glyph.character != "字")
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let interval = PUNCTUATION_MIX_INTERVAL + 1;
+        assert_eq!(marks, [interval - 1, 2 * interval - 1, 3 * interval - 1]);
+        assert_eq!(characters(&mixed[..interval]).last(), Some(&"。"));
+    }
+
+    #[test]
+    fn quotes_close_after_the_chunk_they_open() {
+        let mixed = mix_punctuation(&words(PUNCTUATION_MIX_INTERVAL * 2 * PUNCTUATION.len()));
+        let marks = characters(&mixed)
+            .into_iter()
+            .filter(|character| *character != "字")
+            .collect::<Vec<_>>();
+        // quotes come as a pair with no other mark between them
+        for (index, mark) in marks.iter().enumerate() {
+            match *mark {
+                "“" => assert_eq!(marks[index + 1], "”"),
+                "”" => assert_eq!(marks[index - 1], "“"),
+                _ => {}
+            }
+        }
+        assert_eq!(marks.iter().filter(|mark| **mark == "“").count(), 2);
+        assert_eq!(marks.iter().filter(|mark| **mark == "”").count(), 2);
+    }
+
+    #[test]
+    fn an_open_quote_closes_at_the_end() {
+        // the opening quote is the eighth mark
+        let glyphs = words(PUNCTUATION_MIX_INTERVAL * 8);
+        let mixed = mix_punctuation(&glyphs);
+        assert_eq!(characters(&mixed[mixed.len() - 2..]), ["“", "”"]);
+    }
+
+    #[test]
+    fn punctuation_keys_are_recognised() {
+        assert!(is_punctuation_key('.'));
+        assert!(is_punctuation_key('"'));
+        assert!(is_punctuation_key('\\'));
+        assert!(!is_punctuation_key('a'));
+        assert!(!is_punctuation_key(' '));
+    }
+}
@@ -0,0 +1,82 @@
+use super::{
+    glyph::{AnswerEvent, Glyph},
+    physics::Motion,
+};
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// A glyph the player answered wrongly or too slowly.
+#[derive(Clone)]
+pub struct Mistake {
+    pub glyph: Glyph,
+    /// Wrong codes typed before the right one, in order.
+    pub typed: Vec<String>,
+    pub attempts: usize,
+    pub duration: f32,
+}
+
+/// Mistakes made during the current round, listed on the review page.
+#[derive(Default, Resource)]
+pub struct Review {
+    pub mistakes: Vec<Mistake>,
+}
+
+/// Answers given so far for a ball's current glyph.
+#[derive(Default, Component)]
+pub struct Attempts {
+    pub typed: Vec<String>,
+    pub timestamp: f32,
+    pub mistake: Option<usize>,
+}
+
+/// Starts timing a fresh glyph when its ball becomes active.
+pub fn start_attempts(time: Res<Time>, mut query: Query<&mut Attempts, Added<Motion>>) {
+    for mut attempts in query.iter_mut() {
+        if attempts.typed.is_empty() {
+            attempts.timestamp = time.elapsed_seconds();
+        }
+    }
+}
+
+/// Records glyphs answered wrongly, or correctly after more than [`REVIEW_SLOW_ANSWER_DURATION`].
+pub fn record_answer(
+    time: Res<Time>,
+    mut answer_events: EventReader<AnswerEvent>,
+    mut review: ResMut<Review>,
+    mut query: Query<(&Glyph, &mut Attempts)>,
+) {
+    for event in answer_events.iter() {
+        if let Ok((glyph, mut attempts)) = query.get_mut(event.ball) {
+            let duration = time.elapsed_seconds() - attempts.timestamp;
+            if !event.correct {
+                attempts.typed.push(event.codes.clone());
+            }
+
+            let slow = event.correct && duration > REVIEW_SLOW_ANSWER_DURATION;
+            if !attempts.typed.is_empty() || slow {
+                let mistake = Mistake {
+                    glyph: glyph.clone(),
+                    typed: attempts.typed.clone(),
+                    attempts: attempts.typed.len() + usize::from(event.correct),
+                    duration,
+                };
+
+                // keep updating the same entry until the glyph is answered
+                match attempts.mistake {
+                    Some(index) => review.mistakes[index] = mistake,
+                    None => {
+                        attempts.mistake = Some(review.mistakes.len());
+                        review.mistakes.push(mistake);
+                    }
+                }
+            }
+
+            if event.correct {
+                *attempts = Attempts {
+                    timestamp: time.elapsed_seconds(),
+                    ..Default::default()
+                };
+            }
+        }
+    }
+}
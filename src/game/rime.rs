@@ -0,0 +1,595 @@
+use super::word_list::{spawn_import_panel, ImportPanel};
+use crate::{constants::*, utils::data_dir};
+use bevy::{prelude::*, window::FileDragAndDrop};
+use itertools::Itertools;
+use regex::Regex;
+use std::{collections::HashMap, fs, path::Path};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RimeFile {
+    /// A `*.dict.yaml` table of texts and codes.
+    Dict,
+    /// A `*.schema.yaml`, of which only the speller is used,
+    /// and the labels printed on the keys if it lists them under `key_labels`.
+    Schema,
+}
+
+impl RimeFile {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".dict.yaml") {
+            Some(Self::Dict)
+        } else if name.ends_with(".schema.yaml") {
+            Some(Self::Schema)
+        } else {
+            None
+        }
+    }
+
+    /// Where the imported file is kept between sessions.
+    fn saved_name(&self) -> &'static str {
+        match self {
+            RimeFile::Dict => RIME_DICT_FILE,
+            RimeFile::Schema => RIME_SCHEMA_FILE,
+        }
+    }
+}
+
+pub struct RimeEntry {
+    pub text: String,
+    /// Syllables separated by spaces, as written in the table.
+    pub code: String,
+    pub weight: f32,
+}
+
+pub struct RimeDict {
+    pub name: String,
+    /// Whether the table relies on Rime's preset vocabulary for word weights.
+    /// Only single characters are practised, so the vocabulary itself isn't needed.
+    pub use_preset_vocabulary: bool,
+    pub entries: Vec<RimeEntry>,
+}
+
+/// A spelling algebra rule, applied to each syllable of a code.
+enum Rule {
+    Xform(Regex, String),
+    /// Also covers `abbrev` and `fuzz`, which only differ in how Rime ranks candidates.
+    Derive(Regex, String),
+    Erase(Regex),
+    Xlit(Vec<char>, Vec<char>),
+}
+
+impl Rule {
+    fn parse(rule: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid algebra rule: {rule}");
+        let operator = rule.chars().take_while(|c| c.is_ascii_alphabetic()).count();
+        let (operator, rest) = rule.split_at(operator);
+        let separator = rest.chars().next().ok_or_else(invalid)?;
+        let args = rest[separator.len_utf8()..].split(separator).collect_vec();
+        let regex = |pattern: &str| Regex::new(pattern).map_err(|error| error.to_string());
+        // Rime refers to groups as $1, followed directly by more text
+        let replacement = |replacement: &str| {
+            Regex::new(r"\$(\d)")
+                .unwrap()
+                .replace_all(replacement, "$${$1}")
+                .into_owned()
+        };
+
+        match (operator, args.as_slice()) {
+            ("xform", [pattern, to, ..]) => Ok(Rule::Xform(regex(pattern)?, replacement(to))),
+            ("derive" | "abbrev" | "fuzz", [pattern, to, ..]) => {
+                Ok(Rule::Derive(regex(pattern)?, replacement(to)))
+            }
+            ("erase", [pattern, ..]) => Ok(Rule::Erase(regex(&format!("^(?:{pattern})$"))?)),
+            ("xlit", [from, to, ..]) if from.chars().count() == to.chars().count() => {
+                Ok(Rule::Xlit(from.chars().collect(), to.chars().collect()))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+pub struct Speller {
+    pub name: String,
+    pub alphabet: String,
+    algebra: Vec<Rule>,
+    labels: HashMap<char, String>,
+}
+
+impl Speller {
+    /// Spellings of a syllable, the one derived by `xform` alone first.
+    fn spell(&self, syllable: &str) -> Vec<String> {
+        let mut spellings = vec![syllable.to_string()];
+        for rule in &self.algebra {
+            match rule {
+                Rule::Xform(regex, to) => {
+                    for spelling in spellings.iter_mut() {
+                        *spelling = regex.replace_all(spelling, to.as_str()).into_owned();
+                    }
+                }
+                Rule::Derive(regex, to) => {
+                    let derived = spellings
+                        .iter()
+                        .filter(|spelling| regex.is_match(spelling))
+                        .map(|spelling| regex.replace_all(spelling, to.as_str()).into_owned())
+                        .collect_vec();
+                    spellings.extend(derived);
+                }
+                Rule::Erase(regex) => spellings.retain(|spelling| !regex.is_match(spelling)),
+                Rule::Xlit(from, to) => {
+                    for spelling in spellings.iter_mut() {
+                        *spelling = spelling
+                            .chars()
+                            .map(|c| from.iter().position(|f| *f == c).map_or(c, |i| to[i]))
+                            .collect();
+                    }
+                }
+            }
+        }
+        spellings.into_iter().unique().collect()
+    }
+}
+
+/// Removes YAML quotes and trailing comments from a scalar.
+fn scalar(value: &str) -> &str {
+    let value = value.trim();
+    match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+        _ => value.split(" #").next().unwrap_or_default().trim(),
+    }
+}
+
+/// Splits a Rime file into its YAML header and the table after `...`.
+fn split_header(text: &str) -> (&str, &str) {
+    let text = text.trim_start_matches('\u{feff}');
+    match text.find("\n...") {
+        Some(index) => (&text[..index], &text[index + 4..]),
+        None => (text, ""),
+    }
+}
+
+/// Reads the nested keys of a YAML mapping as paths like `speller/alphabet`,
+/// with list items collected under their key. Enough for Rime headers, not YAML in general.
+fn parse_yaml(text: &str) -> HashMap<String, Vec<String>> {
+    let mut values = HashMap::<String, Vec<String>>::new();
+    let mut path: Vec<(usize, String)> = vec![];
+
+    for line in text.lines() {
+        let content = line.trim_start();
+        if content.is_empty() || content.starts_with('#') || content == "---" {
+            continue;
+        }
+        let indent = line.len() - content.len();
+
+        if let Some(item) = content.strip_prefix("- ") {
+            let key = path.iter().map(|(_, key)| key).join("/");
+            values.entry(key).or_default().push(scalar(item).into());
+            continue;
+        }
+
+        if let Some((key, value)) = content.split_once(':') {
+            while path.last().is_some_and(|(depth, _)| *depth >= indent) {
+                path.pop();
+            }
+            path.push((indent, key.trim().into()));
+
+            let full_key = path.iter().map(|(_, key)| key).join("/");
+            let value = scalar(value);
+            if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                values
+                    .entry(full_key)
+                    .or_default()
+                    .extend(list.split(',').map(|item| scalar(item).to_string()));
+            } else if !value.is_empty() {
+                values.entry(full_key).or_default().push(value.into());
+            }
+        }
+    }
+
+    values
+}
+
+/// Keeps the letters, digits, `_` and `-` of a name, as it ends up in file names and the leaderboard.
+fn sanitize_name(name: &str) -> Option<String> {
+    let name = name
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '_' | '-'))
+        .collect::<String>();
+    (!name.is_empty()).then_some(name)
+}
+
+pub fn parse_dict(text: &str) -> Result<RimeDict, String> {
+    let (header, table) = split_header(text);
+    let header = parse_yaml(header);
+    let first = |key: &str| header.get(key).and_then(|values| values.first());
+
+    let name = first("name")
+        .and_then(|name| sanitize_name(name))
+        .ok_or("The dictionary has no name")?;
+    let columns = header
+        .get("columns")
+        .cloned()
+        .unwrap_or_else(|| vec!["text".into(), "code".into(), "weight".into()]);
+    let column = |name: &str| columns.iter().position(|column| column == name);
+    let (text_column, code_column) = match (column("text"), column("code")) {
+        (Some(text), Some(code)) => (text, code),
+        _ => return Err("The dictionary has no text or code column".into()),
+    };
+    let weight_column = column("weight");
+
+    let entries = table
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let fields = line.split('\t').collect_vec();
+            Some(RimeEntry {
+                text: fields.get(text_column)?.trim().into(),
+                code: fields.get(code_column)?.trim().into(),
+                weight: weight_column
+                    .and_then(|column| fields.get(column))
+                    .and_then(|weight| weight.trim().trim_end_matches('%').parse().ok())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect_vec();
+
+    Ok(RimeDict {
+        name,
+        use_preset_vocabulary: first("use_preset_vocabulary").is_some_and(|value| value == "true"),
+        entries,
+    })
+}
+
+pub fn parse_schema(text: &str) -> Result<Speller, String> {
+    let (header, _) = split_header(text);
+    let schema = parse_yaml(header);
+    let first = |key: &str| schema.get(key).and_then(|values| values.first());
+
+    let name = first("schema/schema_id")
+        .and_then(|name| sanitize_name(name))
+        .or_else(|| first("schema/name").and_then(|name| sanitize_name(name)))
+        .ok_or("The schema has no id")?;
+    let algebra = schema
+        .get("speller/algebra")
+        .into_iter()
+        .flatten()
+        .map(|rule| Rule::parse(rule))
+        .collect::<Result<_, _>>()?;
+    let labels = schema
+        .iter()
+        .filter_map(|(key, values)| {
+            let key = key
+                .strip_prefix("key_labels/")?
+                .chars()
+                .exactly_one()
+                .ok()?;
+            Some((key, values.first()?.clone()))
+        })
+        .collect();
+
+    Ok(Speller {
+        name,
+        alphabet: first("speller/alphabet")
+            .cloned()
+            .unwrap_or_else(|| "abcdefghijklmnopqrstuvwxyz".into()),
+        algebra,
+        labels,
+    })
+}
+
+/// Label of a key bundled for the schema with this id, for schemas that don't list their own.
+fn bundled_key_label(id: &str, key: char) -> Option<String> {
+    let mut schema = false;
+    for line in include_str!("key_labels.tsv").lines() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.split_once('\t') {
+            Some((label_key, label)) if schema && label_key.chars().eq([key]) => {
+                return Some(label.into())
+            }
+            Some(_) => {}
+            None => schema = line.split_whitespace().any(|schema| schema == id),
+        }
+    }
+    None
+}
+
+/// A practice scheme imported from a Rime dictionary, spelled by an imported schema's speller.
+#[derive(Default, Resource)]
+pub struct RimeScheme {
+    pub dict: Option<RimeDict>,
+    pub speller: Option<Speller>,
+    /// Accepted codes by text, most used first.
+    pub codes: HashMap<String, Vec<String>>,
+}
+
+impl RimeScheme {
+    pub fn name(&self) -> &str {
+        match (&self.speller, &self.dict) {
+            (Some(speller), _) => &speller.name,
+            (None, Some(dict)) => &dict.name,
+            (None, None) => "rime",
+        }
+    }
+
+    pub fn alphabet(&self) -> &str {
+        self.speller
+            .as_ref()
+            .map_or("abcdefghijklmnopqrstuvwxyz", |speller| &speller.alphabet)
+    }
+
+    pub fn key_label(&self, key: char) -> String {
+        match &self.speller {
+            Some(speller) if !speller.labels.is_empty() => speller.labels.get(&key).cloned(),
+            _ => bundled_key_label(self.name(), key),
+        }
+        .unwrap_or_default()
+    }
+
+    /// Spells every table entry, keeping all spellings of a single syllable as accepted codes.
+    fn rebuild(&mut self) {
+        self.codes.clear();
+        let dict = match &self.dict {
+            Some(dict) => dict,
+            None => return,
+        };
+
+        let mut weighted = HashMap::<&str, Vec<(f32, String)>>::new();
+        for entry in &dict.entries {
+            let syllables = entry
+                .code
+                .split_whitespace()
+                .map(|syllable| match &self.speller {
+                    Some(speller) => speller.spell(syllable),
+                    None => vec![syllable.to_string()],
+                })
+                .collect_vec();
+
+            let codes = match syllables.as_slice() {
+                [spellings] => spellings.clone(),
+                _ => syllables
+                    .iter()
+                    .map(|spellings| spellings.first().cloned())
+                    .collect::<Option<String>>()
+                    .into_iter()
+                    .collect(),
+            };
+            weighted
+                .entry(&entry.text)
+                .or_default()
+                .extend(codes.into_iter().map(|code| (entry.weight, code)));
+        }
+
+        self.codes = weighted
+            .into_iter()
+            .map(|(text, codes)| {
+                let codes = codes
+                    .into_iter()
+                    .sorted_by(|(a, _), (b, _)| b.total_cmp(a))
+                    .map(|(_, code)| code)
+                    .unique()
+                    .collect();
+                (text.to_string(), codes)
+            })
+            .collect();
+    }
+
+    fn load(&mut self, kind: RimeFile, text: &str) -> Result<(), String> {
+        match kind {
+            RimeFile::Dict => self.dict = Some(parse_dict(text)?),
+            RimeFile::Schema => self.speller = Some(parse_schema(text)?),
+        }
+        self.rebuild();
+        Ok(())
+    }
+}
+
+/// Restores the Rime files imported in an earlier session.
+pub fn load_rime(mut rime: ResMut<RimeScheme>) {
+    let dir = match data_dir() {
+        Some(dir) => dir,
+        None => return,
+    };
+    for kind in [RimeFile::Schema, RimeFile::Dict] {
+        if let Ok(text) = fs::read_to_string(dir.join(kind.saved_name())) {
+            if let Err(error) = rime.load(kind, &text) {
+                warn!("Failed to load the Rime {}: {error}", kind.saved_name());
+            }
+        }
+    }
+}
+
+/// Imports a Rime dictionary or schema dropped onto the window.
+pub fn import_rime(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut rime: ResMut<RimeScheme>,
+    mut events: EventReader<FileDragAndDrop>,
+    panels: Query<Entity, With<ImportPanel>>,
+) {
+    for event in events.iter() {
+        let path = match event {
+            FileDragAndDrop::DroppedFile { path_buf, .. } => path_buf,
+            _ => continue,
+        };
+        let kind = match RimeFile::from_path(path) {
+            Some(kind) => kind,
+            None => continue,
+        };
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+        let result = fs::read_to_string(path)
+            .map_err(|error| error.to_string())
+            .and_then(|text| {
+                rime.load(kind, &text)?;
+                Ok(text)
+            });
+        let details = match &rime.dict {
+            Some(dict)
+                if result.is_ok() && kind == RimeFile::Dict && dict.use_preset_vocabulary =>
+            {
+                "\nWords from the preset vocabulary are skipped, only characters are practised"
+                    .into()
+            }
+            _ => String::new(),
+        };
+        let summary = match result {
+            Ok(text) => {
+                let saved = data_dir().ok_or_else(|| "no data directory".to_string());
+                if let Err(error) = saved.and_then(|dir| {
+                    fs::create_dir_all(&dir)
+                        .and_then(|_| fs::write(dir.join(kind.saved_name()), text))
+                        .map_err(|error| error.to_string())
+                }) {
+                    warn!("Failed to save the Rime file: {error}");
+                }
+                match kind {
+                    RimeFile::Dict => format!(
+                        "Imported {} codes from {name}\nPick \"rime\" in Settings to practise {}",
+                        rime.codes.len(),
+                        rime.name()
+                    ),
+                    RimeFile::Schema => format!("Imported the speller of {}", rime.name()),
+                }
+            }
+            Err(error) => format!("Failed to import {name}: {error}"),
+        };
+
+        spawn_import_panel(&mut commands, &asset_server, &panels, summary, details);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheme(schema: &str) -> RimeScheme {
+        RimeScheme {
+            speller: Some(parse_schema(schema).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    fn speller(algebra: &[&str]) -> Speller {
+        Speller {
+            name: "test".into(),
+            alphabet: "abcdefghijklmnopqrstuvwxyz".into(),
+            algebra: algebra
+                .iter()
+                .map(|rule| Rule::parse(rule).unwrap())
+                .collect(),
+            labels: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn rules_parse_with_any_separator() {
+        assert!(matches!(
+            Rule::parse("xform/^([zcs])h/$1/"),
+            Ok(Rule::Xform(..))
+        ));
+        assert!(matches!(Rule::parse("derive|ng$|n|"), Ok(Rule::Derive(..))));
+        assert!(matches!(
+            Rule::parse("abbrev/^(.).+$/$1/"),
+            Ok(Rule::Derive(..))
+        ));
+        assert!(matches!(Rule::parse("erase/^xx$/"), Ok(Rule::Erase(..))));
+        assert!(matches!(Rule::parse("xlit/abc/xyz/"), Ok(Rule::Xlit(..))));
+        assert!(Rule::parse("xlit/abc/xy/").is_err());
+        assert!(Rule::parse("shuffle/a/b/").is_err());
+        assert!(Rule::parse("xform/(/x/").is_err());
+    }
+
+    #[test]
+    fn speller_applies_rules_in_order() {
+        let speller = speller(&[
+            "derive/^([zcs])h/$1/",
+            "xform/ang$/AN/",
+            "xlit/AN/an/",
+            "erase/^san$/",
+        ]);
+        assert_eq!(speller.spell("zhang"), ["zhan", "zan"]);
+        assert_eq!(speller.spell("shang"), ["shan"]);
+        assert_eq!(speller.spell("ma"), ["ma"]);
+    }
+
+    #[test]
+    fn yaml_keys_nest_into_paths() {
+        let yaml = parse_yaml(
+            "# comment\n---\nschema:\n  schema_id: luna # id\n  name: \"Luna: pinyin\"\n\
+             speller:\n  alphabet: 'abc'\n  algebra:\n    - xform/a/b/\n    - erase/c/\n\
+             columns: [text, code]\n",
+        );
+        let get = |key: &str| yaml.get(key).cloned().unwrap_or_default();
+        assert_eq!(get("schema/schema_id"), ["luna"]);
+        assert_eq!(get("schema/name"), ["Luna: pinyin"]);
+        assert_eq!(get("speller/alphabet"), ["abc"]);
+        assert_eq!(get("speller/algebra"), ["xform/a/b/", "erase/c/"]);
+        assert_eq!(get("columns"), ["text", "code"]);
+        assert!(!yaml.contains_key("schema"));
+    }
+
+    #[test]
+    fn dict_reads_its_columns() {
+        let dict = parse_dict(
+            "---\nname: my.dict\ncolumns:\n  - code\n  - weight\n  - text\n\
+             use_preset_vocabulary: true\n...\n\
+             # comment\nzhong\t10%\t中\nzhong guo\t5\t中国\nhao\n",
+        )
+        .unwrap();
+        assert_eq!(dict.name, "mydict");
+        assert!(dict.use_preset_vocabulary);
+        let entries = dict
+            .entries
+            .iter()
+            .map(|entry| (entry.text.as_str(), entry.code.as_str(), entry.weight))
+            .collect_vec();
+        assert_eq!(entries, [("中", "zhong", 10.0), ("中国", "zhong guo", 5.0)]);
+    }
+
+    #[test]
+    fn dict_columns_default_to_text_code_weight() {
+        let dict = parse_dict("name: plain\n...\n中\tzhong\n").unwrap();
+        assert!(!dict.use_preset_vocabulary);
+        assert_eq!(dict.entries[0].code, "zhong");
+        assert_eq!(dict.entries[0].weight, 0.0);
+
+        assert!(parse_dict("columns: [text]\nname: x\n...\n").is_err());
+        assert!(parse_dict("...\n中\tzhong\n").is_err());
+    }
+
+    #[test]
+    fn codes_are_spelled_and_weighted() {
+        let mut rime = RimeScheme::default();
+        rime.load(
+            RimeFile::Schema,
+            "schema:\n  schema_id: test\nspeller:\n  algebra:\n    - derive/^zh/z/\n",
+        )
+        .unwrap();
+        rime.load(
+            RimeFile::Dict,
+            "name: test\n...\n中\tzhong\t1\n中\tzhòng\t9\n中国\tzhong guo\t1\n",
+        )
+        .unwrap();
+        assert_eq!(rime.codes["中"], ["zhòng", "zòng", "zhong", "zong"]);
+        assert_eq!(rime.codes["中国"], ["zhongguo"]);
+    }
+
+    #[test]
+    fn key_labels_come_from_the_schema() {
+        let rime = scheme("schema:\n  schema_id: cangjie5\nkey_labels:\n  a: 日\n  b: \"月\"\n");
+        assert_eq!(rime.key_label('a'), "日");
+        assert_eq!(rime.key_label('b'), "月");
+        assert_eq!(rime.key_label('c'), "");
+    }
+
+    #[test]
+    fn key_labels_are_bundled_by_schema_id() {
+        assert_eq!(
+            scheme("schema:\n  schema_id: wubi86\n").key_label('g'),
+            "王 一 五"
+        );
+        assert_eq!(scheme("schema:\n  schema_id: wubi98\n").key_label('g'), "");
+    }
+}
@@ -0,0 +1,247 @@
+use super::{
+    fuzzy::{FuzzyPinyin, FuzzyRule},
+    glyph::Glyph,
+    punctuation::is_punctuation_key,
+    rime::RimeScheme,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use std::marker::PhantomData;
+
+/// The input method whose codes are practised.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// Full pinyin without tones, e.g. `zhong` for 中.
+    #[default]
+    Pinyin,
+    /// Xiaohe shuangpin, one key for the initial and one for the final, e.g. `vs` for 中.
+    Xiaohe,
+    /// Codes from an imported Rime dictionary, see [`RimeScheme`].
+    Rime,
+}
+
+/// Xiaohe shuangpin keys with the initial and the finals typed on each.
+const XIAOHE_KEYS: [(char, &str, &[&str]); 26] = [
+    ('q', "q", &["iu"]),
+    ('w', "w", &["ei"]),
+    ('e', "", &["e"]),
+    ('r', "r", &["uan", "er"]),
+    ('t', "t", &["ue", "ve"]),
+    ('y', "y", &["un"]),
+    ('u', "sh", &["u"]),
+    ('i', "ch", &["i"]),
+    ('o', "", &["uo", "o"]),
+    ('p', "p", &["ie"]),
+    ('a', "", &["a"]),
+    ('s', "s", &["ong", "iong"]),
+    ('d', "d", &["ai"]),
+    ('f', "f", &["en"]),
+    ('g', "g", &["eng"]),
+    ('h', "h", &["ang"]),
+    ('j', "j", &["an"]),
+    ('k', "k", &["ing", "uai"]),
+    ('l', "l", &["iang", "uang"]),
+    ('z', "z", &["ou"]),
+    ('x', "x", &["ia", "ua"]),
+    ('c', "c", &["ao"]),
+    ('v', "zh", &["ui", "v"]),
+    ('b', "b", &["in"]),
+    ('n', "n", &["iao"]),
+    ('m', "m", &["ian"]),
+];
+
+impl Scheme {
+    pub const ALL: [Scheme; 3] = [Scheme::Pinyin, Scheme::Xiaohe, Scheme::Rime];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scheme::Pinyin => "pinyin",
+            Scheme::Xiaohe => "xiaohe",
+            Scheme::Rime => "rime",
+        }
+    }
+
+    /// Whether a typed character can be part of a code.
+    pub fn accepts(&self, char: char) -> bool {
+        char.is_ascii_lowercase() || is_punctuation_key(char)
+    }
+
+    /// Converts a toneless pinyin code to the keys typed in this scheme.
+    /// Codes that aren't pinyin syllables are returned unchanged.
+    pub fn encode(&self, code: &str) -> String {
+        match self {
+            Scheme::Pinyin | Scheme::Rime => code.into(),
+            Scheme::Xiaohe => xiaohe_encode(code).unwrap_or_else(|| code.into()),
+        }
+    }
+
+    /// Roots or finals printed on a key of the on-screen keyboard.
+    pub fn key_label(&self, key: char) -> String {
+        match self {
+            Scheme::Pinyin | Scheme::Rime => String::new(),
+            Scheme::Xiaohe => XIAOHE_KEYS
+                .iter()
+                .find(|(k, ..)| *k == key)
+                .map(|(_, initial, finals)| {
+                    let initial = (initial.len() > 1).then_some(*initial);
+                    initial
+                        .into_iter()
+                        .chain(finals.iter().copied())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The selected [`Scheme`], together with the imported Rime table it may refer to.
+#[derive(SystemParam)]
+pub struct ActiveScheme<'w, 's> {
+    scheme: Res<'w, Scheme>,
+    rime: Res<'w, RimeScheme>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+impl ActiveScheme<'_, '_> {
+    /// Name of the scheme, or of the imported Rime schema.
+    pub fn name(&self) -> &str {
+        match *self.scheme {
+            Scheme::Rime => self.rime.name(),
+            scheme => scheme.name(),
+        }
+    }
+
+    /// Codes accepted for a glyph, the most common first.
+    /// Glyphs missing from a Rime table keep their own codes, which covers punctuation.
+    pub fn codes(&self, glyph: &Glyph) -> Vec<String> {
+        match *self.scheme {
+            Scheme::Rime => self
+                .rime
+                .codes
+                .get(&glyph.character)
+                .cloned()
+                .unwrap_or_else(|| glyph.codes.clone()),
+            scheme => glyph
+                .codes
+                .iter()
+                .map(|code| {
+                    // words are typed a syllable at a time
+                    let syllables = glyph.syllables();
+                    match syllables.len() > 1 && syllables.concat() == *code {
+                        true => syllables.iter().map(|code| scheme.encode(code)).collect(),
+                        false => scheme.encode(code),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Fuzzy rules a typed code needs to be accepted for a glyph, or `None` if they aren't enough.
+    /// Only pinyin is spelled fuzzily, so codes from a Rime table must match exactly.
+    pub fn fuzzy_rules(
+        &self,
+        glyph: &Glyph,
+        typed: &str,
+        fuzzy: &FuzzyPinyin,
+    ) -> Option<Vec<FuzzyRule>> {
+        if *self.scheme == Scheme::Rime {
+            return None;
+        }
+        self.fuzzy_variants(glyph, fuzzy)
+            .find(|(code, _)| code == typed)
+            .map(|(_, rules)| rules)
+    }
+
+    /// Codes typed for a glyph with the enabled fuzzy rules, besides its own.
+    pub fn fuzzy_codes(&self, glyph: &Glyph, fuzzy: &FuzzyPinyin) -> Vec<String> {
+        if *self.scheme == Scheme::Rime {
+            return vec![];
+        }
+        self.fuzzy_variants(glyph, fuzzy)
+            .map(|(code, _)| code)
+            .collect()
+    }
+
+    /// Fuzzy spellings of the pinyin codes of a glyph, encoded, with the rules each one needs.
+    /// Words are spelled a syllable at a time, so the rules apply to each of their syllables.
+    fn fuzzy_variants<'a>(
+        &'a self,
+        glyph: &'a Glyph,
+        fuzzy: &'a FuzzyPinyin,
+    ) -> impl Iterator<Item = (String, Vec<FuzzyRule>)> + 'a {
+        let syllables = glyph.syllables();
+        glyph.codes.iter().flat_map(move |code| {
+            let word = match syllables.len() > 1 && syllables.concat() == *code {
+                true => syllables.clone(),
+                false => vec![code.as_str()],
+            };
+            fuzzy
+                .word_variants(&word)
+                .into_iter()
+                .map(|(spellings, rules)| {
+                    let code = spellings.iter().map(|code| self.scheme.encode(code));
+                    (code.collect(), rules)
+                })
+        })
+    }
+
+    pub fn first_code(&self, glyph: &Glyph) -> Option<String> {
+        self.codes(glyph).into_iter().next()
+    }
+
+    pub fn accepts(&self, char: char) -> bool {
+        match *self.scheme {
+            Scheme::Rime => self.rime.alphabet().contains(char) || is_punctuation_key(char),
+            scheme => scheme.accepts(char),
+        }
+    }
+
+    pub fn key_label(&self, key: char) -> String {
+        match *self.scheme {
+            Scheme::Rime => self.rime.key_label(key),
+            scheme => scheme.key_label(key),
+        }
+    }
+}
+
+fn xiaohe_final(final_: &str) -> Option<char> {
+    XIAOHE_KEYS
+        .iter()
+        .find(|(_, _, finals)| finals.contains(&final_))
+        .map(|(key, ..)| *key)
+}
+
+fn xiaohe_encode(code: &str) -> Option<String> {
+    if !code.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+
+    let initial = ["zh", "ch", "sh"]
+        .into_iter()
+        .find(|initial| code.starts_with(initial))
+        .or_else(|| {
+            let first = code.get(..1)?;
+            "bpmfdtnlgkhjqxrzcsyw".contains(first).then_some(first)
+        });
+
+    match initial {
+        Some(initial) => {
+            let final_ = &code[initial.len()..];
+            let initial = XIAOHE_KEYS
+                .iter()
+                .find(|(_, i, _)| *i == initial)
+                .map(|(key, ..)| *key)?;
+            Some([initial, xiaohe_final(final_)?].iter().collect())
+        }
+        // syllables without an initial keep their first letter
+        None => match code.len() {
+            1 => Some(code.repeat(2)),
+            2 => Some(code.into()),
+            _ => {
+                let first = code.chars().next()?;
+                Some([first, xiaohe_final(code)?].iter().collect())
+            }
+        },
+    }
+}
@@ -0,0 +1,392 @@
+use super::{
+    deck::{CharacterSet, Decks},
+    fuzzy::FuzzyPinyin,
+    glyph::{Glyph, GlyphQueue, GlyphTable},
+    player::{ConfirmEvent, InputCodes},
+    scheme::ActiveScheme,
+    Cleanup, TypingMode,
+};
+use crate::constants::*;
+use bevy::prelude::*;
+use itertools::Itertools;
+use std::collections::HashSet;
+
+/// The glyphs of the queue shown together in [`TypingMode::Sentence`], and the codes they're typed with.
+#[derive(Default, Resource)]
+pub struct Sentence {
+    /// Index in the [`GlyphQueue`] of the first glyph of the sentence.
+    pub start: usize,
+    /// Indices in the queue where each sentence ends, filled along with the queue.
+    /// Without them sentences are [`SENTENCE_LENGTH`] glyphs long.
+    pub ends: Vec<usize>,
+    /// Codes continuous input is split into: those of the glyphs of the sentence, fuzzy spellings included.
+    pub codes: HashSet<String>,
+    /// Beginnings of the codes, for input that may still grow into one.
+    pub prefixes: HashSet<String>,
+}
+
+impl Sentence {
+    /// Fills the queue with sentences one after another, remembering where each ends.
+    pub fn queue(&mut self, sentences: Vec<Vec<Glyph>>, queue: &mut GlyphQueue) {
+        *queue = GlyphQueue::default();
+        self.ends.clear();
+        for sentence in sentences {
+            queue.glyphs.extend(sentence);
+            self.ends.push(queue.glyphs.len());
+        }
+    }
+
+    /// Index in the queue just past the last glyph of the sentence.
+    fn end(&self) -> usize {
+        match self.ends.is_empty() {
+            true => self.start + SENTENCE_LENGTH,
+            false => self
+                .ends
+                .iter()
+                .copied()
+                .find(|end| *end > self.start)
+                .unwrap_or(usize::MAX),
+        }
+    }
+
+    /// Moves to the sentence holding a glyph of the queue, and collects the codes of its glyphs.
+    fn start_at(
+        &mut self,
+        index: usize,
+        queue: &GlyphQueue,
+        scheme: &ActiveScheme,
+        fuzzy: &FuzzyPinyin,
+    ) {
+        self.start = match self.ends.is_empty() {
+            true => index - index % SENTENCE_LENGTH,
+            false => self
+                .ends
+                .iter()
+                .copied()
+                .filter(|end| *end <= index)
+                .max()
+                .unwrap_or_default(),
+        };
+
+        self.codes.clear();
+        self.prefixes.clear();
+        let codes = self
+            .glyphs(queue)
+            .iter()
+            .flat_map(|glyph| {
+                let fuzzy = scheme.fuzzy_codes(glyph, fuzzy);
+                scheme.codes(glyph).into_iter().chain(fuzzy)
+            })
+            .collect_vec();
+        for code in codes {
+            self.add_code(code);
+        }
+    }
+
+    fn add_code(&mut self, code: String) {
+        for (index, _) in code.char_indices().skip(1) {
+            self.prefixes.insert(code[..index].into());
+        }
+        self.codes.insert(code);
+    }
+
+    /// Splits a part of the input between apostrophes into codes.
+    /// Longer codes are tried first, as long as the rest can still be split;
+    /// at the end of the input the last code may be unfinished.
+    fn split<'a>(&self, chunk: &'a str, end: bool) -> Option<Vec<&'a str>> {
+        if chunk.is_empty() {
+            return Some(vec![]);
+        }
+        for (index, char) in chunk.char_indices().rev() {
+            let (head, rest) = chunk.split_at(index + char.len_utf8());
+            if rest.is_empty() && end && self.prefixes.contains(head) {
+                return Some(vec![head]);
+            }
+            if self.codes.contains(head) {
+                if let Some(mut codes) = self.split(rest, end) {
+                    codes.insert(0, head);
+                    return Some(codes);
+                }
+            }
+        }
+        None
+    }
+
+    /// Codes of continuous input such as `xi'anshi`, or `None` if it can't be split.
+    pub fn segment<'a>(&self, input: &'a str) -> Option<Vec<&'a str>> {
+        let chunks: Vec<_> = input.split('\'').collect();
+        let mut codes = vec![];
+        for (index, chunk) in chunks.iter().enumerate() {
+            codes.extend(self.split(chunk, index == chunks.len() - 1)?);
+        }
+        Some(codes)
+    }
+
+    /// The first code of the input once typing more can't change it, with the length of input it took.
+    /// That is when it's followed by an apostrophe or another code, or when no longer code starts with it.
+    /// Input that can't be split is taken whole up to the next apostrophe, so it's answered wrongly
+    /// rather than blocking the input.
+    pub fn resolve<'a>(&self, input: &'a str) -> Option<(&'a str, usize)> {
+        let trimmed = input.trim_start_matches('\'');
+        let skipped = input.len() - trimmed.len();
+
+        if let Some((chunk, _)) = trimmed.split_once('\'') {
+            let code = self
+                .split(chunk, false)
+                .and_then(|codes| codes.first().copied())
+                .unwrap_or(chunk);
+            // drop the apostrophe along with the last code before it
+            let length = match code.len() == chunk.len() {
+                true => code.len() + 1,
+                false => code.len(),
+            };
+            return Some((code, skipped + length));
+        }
+
+        let codes = match self.segment(trimmed) {
+            Some(codes) => codes,
+            None => return Some((trimmed, input.len())),
+        };
+        match codes.as_slice() {
+            [code, _, ..] => Some((code, skipped + code.len())),
+            [code] if self.codes.contains(*code) && !self.prefixes.contains(*code) => {
+                Some((code, skipped + code.len()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Glyphs of the sentence, up to its end or the end of the queue.
+    pub fn glyphs<'a>(&self, queue: &'a GlyphQueue) -> &'a [Glyph] {
+        let end = self.end().min(queue.glyphs.len());
+        queue.glyphs.get(self.start..end).unwrap_or_default()
+    }
+}
+
+/// Sentences of a Sentence round: the word list cut into [`SENTENCE_LENGTH`] glyphs, else the bundled
+/// sentences whose characters are all in the set, else the set cut the same way.
+pub fn sentences(
+    word_list: &[Glyph],
+    set: &CharacterSet,
+    table: &GlyphTable,
+    decks: &Decks,
+) -> Vec<Vec<Glyph>> {
+    let cut = |glyphs: &[Glyph]| glyphs.chunks(SENTENCE_LENGTH).map(<[_]>::to_vec).collect();
+    if !word_list.is_empty() {
+        return cut(word_list);
+    }
+
+    let bundled = include_str!("sentences.txt")
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+        .filter_map(|line| {
+            table
+                .read(line.trim())
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()
+                .ok()
+        })
+        .filter(|glyphs| set.filter(glyphs, table, decks).len() == glyphs.len())
+        .collect_vec();
+    match bundled.is_empty() {
+        true => cut(&set.glyphs(table, decks)),
+        false => bundled,
+    }
+}
+
+#[derive(Component)]
+pub struct SentenceText;
+
+/// Collects the codes input is segmented with, and shows the sentence at the top of the arena.
+pub fn make_sentence(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    typing_mode: Res<TypingMode>,
+    scheme: ActiveScheme,
+    fuzzy: Res<FuzzyPinyin>,
+    queue: Res<GlyphQueue>,
+    mut sentence: ResMut<Sentence>,
+) {
+    if *typing_mode != TypingMode::Sentence {
+        return;
+    }
+    sentence.start_at(queue.index, &queue, &scheme, &fuzzy);
+
+    commands.spawn((
+        TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(SENTENCE_TOP),
+                    left: Val::Px(16.0),
+                    right: Val::Px(16.0),
+                    ..Default::default()
+                },
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load(FONT_LXGW),
+                    font_size: 32.0,
+                    color: SENTENCE_PENDING_COLOR,
+                },
+            )
+            .with_alignment(TextAlignment::CENTER),
+            ..Default::default()
+        },
+        SentenceText,
+        Cleanup,
+    ));
+}
+
+/// Confirms the first code of the input as soon as it is resolved, then drops it from the input.
+/// Only one code a frame, so the ball has moved on to the next glyph before the next one is checked.
+pub fn segment_input(
+    typing_mode: Res<TypingMode>,
+    sentence: Res<Sentence>,
+    mut confirm_events: EventWriter<ConfirmEvent>,
+    mut query: Query<&mut Text, With<InputCodes>>,
+) {
+    if *typing_mode != TypingMode::Sentence {
+        return;
+    }
+    let mut text = match query.get_single_mut() {
+        Ok(text) => text,
+        Err(_) => return,
+    };
+
+    let input = &mut text.sections[0].value;
+    if let Some((code, length)) = sentence.resolve(input) {
+        confirm_events.send(ConfirmEvent { codes: code.into() });
+        input.replace_range(..length, "");
+    }
+}
+
+/// Moves on to the next sentence once the queue leaves this one,
+/// and colors the answered glyphs and those the input covers so far.
+pub fn sentence_system(
+    scheme: ActiveScheme,
+    fuzzy: Res<FuzzyPinyin>,
+    queue: Res<GlyphQueue>,
+    mut sentence: ResMut<Sentence>,
+    input_query: Query<&Text, (With<InputCodes>, Without<SentenceText>)>,
+    mut query: Query<&mut Text, With<SentenceText>>,
+) {
+    let mut text = match query.get_single_mut() {
+        Ok(text) => text,
+        Err(_) => return,
+    };
+    if !(sentence.start..sentence.end()).contains(&queue.index) {
+        sentence.start_at(queue.index, &queue, &scheme, &fuzzy);
+    }
+
+    let answered = queue.index - sentence.start;
+    let covered = input_query
+        .get_single()
+        .ok()
+        .and_then(|input| sentence.segment(&input.sections[0].value))
+        .map_or(0, |codes| codes.len());
+    let style = text.sections[0].style.clone();
+
+    text.sections = sentence
+        .glyphs(&queue)
+        .iter()
+        .enumerate()
+        .map(|(index, glyph)| {
+            let color = if index < answered {
+                SENTENCE_ANSWERED_COLOR
+            } else if index < answered + covered {
+                SENTENCE_COVERED_COLOR
+            } else {
+                SENTENCE_PENDING_COLOR
+            };
+            TextSection::new(
+                glyph.character.clone(),
+                TextStyle {
+                    color,
+                    ..style.clone()
+                },
+            )
+        })
+        .collect();
+
+    // keep a section for the style of the next sentence
+    if text.sections.is_empty() {
+        text.sections.push(TextSection::new("", style));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentence(codes: &[&str]) -> Sentence {
+        let mut sentence = Sentence::default();
+        for code in codes {
+            sentence.add_code(code.to_string());
+        }
+        sentence
+    }
+
+    #[test]
+    fn continuous_input_splits_into_codes() {
+        let sentence = sentence(&["wo", "ai", "zhong", "guo"]);
+        assert_eq!(
+            sentence.segment("woaizhongguo"),
+            Some(vec!["wo", "ai", "zhong", "guo"])
+        );
+        // the last code may still be typed
+        assert_eq!(sentence.segment("woaizh"), Some(vec!["wo", "ai", "zh"]));
+        assert_eq!(sentence.segment("woxx"), None);
+    }
+
+    #[test]
+    fn apostrophes_split_xian_from_xi_an() {
+        let sentence = sentence(&["xi", "an", "xian", "shi"]);
+        assert_eq!(sentence.segment("xianshi"), Some(vec!["xian", "shi"]));
+        assert_eq!(sentence.segment("xi'anshi"), Some(vec!["xi", "an", "shi"]));
+
+        let sentence = self::sentence(&["xi", "an", "shi"]);
+        assert_eq!(sentence.segment("xianshi"), Some(vec!["xi", "an", "shi"]));
+    }
+
+    #[test]
+    fn codes_resolve_once_more_input_cant_change_them() {
+        let sentence = sentence(&["xi", "an", "xian", "shi"]);
+        // `xi` may still become `xian`
+        assert_eq!(sentence.resolve("xi"), None);
+        assert_eq!(sentence.resolve("xia"), None);
+        assert_eq!(sentence.resolve("xians"), Some(("xian", 4)));
+        assert_eq!(sentence.resolve("xi'an"), Some(("xi", 3)));
+        assert_eq!(sentence.resolve("'an"), Some(("an", 3)));
+        assert_eq!(sentence.resolve("shi"), Some(("shi", 3)));
+        // input that can't be split is taken whole
+        assert_eq!(sentence.resolve("qq"), Some(("qq", 2)));
+        assert_eq!(sentence.resolve("qq'shi"), Some(("qq", 3)));
+    }
+
+    #[test]
+    fn sentences_end_where_they_were_queued() {
+        let glyph = |character| Glyph::new(character, "", &[]);
+        let mut sentence = Sentence::default();
+        let mut queue = GlyphQueue::default();
+        sentence.queue(
+            vec![
+                vec![glyph("你"), glyph("好")],
+                vec![glyph("再"), glyph("见"), glyph("了")],
+            ],
+            &mut queue,
+        );
+        assert_eq!(sentence.ends, [2, 5]);
+
+        sentence.start = 2;
+        let characters = sentence
+            .glyphs(&queue)
+            .iter()
+            .map(|glyph| glyph.character.as_str())
+            .collect_vec();
+        assert_eq!(characters, ["再", "见", "了"]);
+    }
+}
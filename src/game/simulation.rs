@@ -0,0 +1,198 @@
+use super::*;
+use bevy::{
+    ecs::{
+        event::{Events, ManualEventReader},
+        schedule::SingleThreadedExecutor,
+    },
+    input::mouse::MouseMotion,
+    time::{FixedTimesteps, TimePlugin},
+    utils::Instant,
+};
+use std::time::Duration;
+
+/// Game mode run by a headless [`Simulation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulationMode {
+    /// Ends as soon as the game is won or lost.
+    Battle,
+    /// No enemy, infinite balls, and the enemy base heals after it's destroyed.
+    Practice,
+    /// Practice with slit blocks, as in [`PracticeState::Slits`].
+    PracticeSlits,
+}
+
+/// Game logic run without a window or renderer: the arena, paddles and ball,
+/// with the player moved by scripted mouse motion and helped by the assist as usual,
+/// and the glyph on the ball answered by scripted typing.
+pub struct Simulation {
+    pub mode: SimulationMode,
+    /// Number of fixed physics steps to run, each one a frame.
+    pub ticks: usize,
+    /// Mouse motion of each tick, repeated once it runs out; none leaves the player to the assist.
+    pub script: Vec<Vec2>,
+    /// Text typed at some ticks, a space confirming the codes typed before it.
+    pub typing: Vec<(usize, String)>,
+    /// The glyph carried by the ball.
+    pub glyph: Glyph,
+    /// Seed of the random numbers, such as where the slits move to.
+    pub seed: u64,
+}
+
+pub struct SimulationResult {
+    pub score: Score,
+    pub enemy_hp: f32,
+    /// Ticks run, fewer than asked for if a battle was over before.
+    pub ticks: usize,
+}
+
+/// Runs a simulation to the end and returns the score it made.
+pub fn simulate(simulation: &Simulation) -> SimulationResult {
+    fastrand::seed(simulation.seed);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins.build().disable::<TimePlugin>())
+        .add_plugin(TransformPlugin)
+        .init_resource::<Time>()
+        .init_resource::<FixedTimesteps>()
+        .init_resource::<TimeScale>()
+        .init_resource::<Score>()
+        .init_resource::<Debounce>()
+        .init_resource::<TypingMode>()
+        .init_resource::<Slits>()
+        .init_resource::<Scheme>()
+        .init_resource::<RimeScheme>()
+        .init_resource::<FuzzyPinyin>()
+        .add_event::<MouseMotion>()
+        .add_event::<TypedEvent>()
+        .add_event::<ConfirmEvent>()
+        .add_event::<GameOverEvent>()
+        .add_event::<PlayerHitEvent>()
+        .add_event::<PlayerMissEvent>()
+        .add_event::<HealEvent>()
+        .add_event::<AnswerEvent>()
+        .add_system_set(play_systems())
+        .add_system_set(ai_systems())
+        .add_plugin(PhysicsPlugin);
+
+    // dictation pronounces balls as they become active
+    #[cfg(feature = "audio")]
+    app.add_event::<PronounceEvent>();
+
+    match simulation.mode {
+        SimulationMode::Battle => app.add_system_set(battle_systems()),
+        SimulationMode::Practice | SimulationMode::PracticeSlits => app.add_system_set(
+            endless_systems().with_system(change_slits.after(player_ball_infinite)),
+        ),
+    };
+
+    // and they run on this thread, so runs with the same seed give the same score
+    for label in [
+        CoreStage::First,
+        CoreStage::PreUpdate,
+        CoreStage::Update,
+        CoreStage::PostUpdate,
+        CoreStage::Last,
+    ] {
+        app.stage(label, |stage: &mut SystemStage| {
+            stage.set_executor(Box::<SingleThreadedExecutor>::default());
+            stage
+        });
+    }
+
+    spawn_bodies(&mut app.world, simulation);
+    app.world
+        .spawn((Text::from_section("", default()), InputCodes));
+    app.world
+        .resource_mut::<Events<HealEvent>>()
+        .send(HealEvent(Heal::default()));
+
+    // every frame lasts a physics step, rounded up so the fixed time step never falls short
+    let step = Duration::from_nanos((PHYSICS_TIME_STEP as f64 * 1e9).ceil() as u64);
+    let mut instant = Instant::now();
+    app.world
+        .resource_mut::<Time>()
+        .update_with_instant(instant);
+
+    let mut ticks = 0;
+    let mut game_over = ManualEventReader::<GameOverEvent>::default();
+    while ticks < simulation.ticks {
+        if let Some(delta) = simulation
+            .script
+            .get(ticks % simulation.script.len().max(1))
+        {
+            let delta = *delta;
+            app.world.send_event(MouseMotion { delta });
+        }
+        let timestamp = app.world.resource::<Time>().elapsed_seconds();
+        for (_, text) in simulation.typing.iter().filter(|(tick, _)| *tick == ticks) {
+            app.world
+                .send_event_batch(text.chars().map(|char| TypedEvent { char, timestamp }));
+        }
+
+        instant += step;
+        app.world
+            .resource_mut::<Time>()
+            .update_with_instant(instant);
+        app.update();
+        ticks += 1;
+
+        let events = app.world.resource::<Events<GameOverEvent>>();
+        if simulation.mode == SimulationMode::Battle && game_over.iter(events).next().is_some() {
+            break;
+        }
+    }
+
+    let enemy_hp = app
+        .world
+        .query::<&EnemyBase>()
+        .iter(&app.world)
+        .map(|base| base.hp)
+        .sum();
+    let score = app.world.remove_resource::<Score>().unwrap();
+
+    SimulationResult {
+        score,
+        enemy_hp,
+        ticks,
+    }
+}
+
+/// The bodies of [`make_arena`], [`make_player`], [`make_enemy`] and [`make_ball`], without anything to render.
+fn spawn_bodies(world: &mut World, simulation: &Simulation) {
+    world.spawn(arena_body());
+    world.spawn(separate_body());
+    world.spawn(top_boundary_body());
+    world.spawn(bottom_boundary_body());
+    world.spawn(side_boundary_body(-1.0));
+    world.spawn(side_boundary_body(1.0));
+    world.spawn(player_body());
+
+    if simulation.mode == SimulationMode::Battle {
+        world.spawn(enemy_body());
+    }
+
+    if simulation.mode == SimulationMode::PracticeSlits {
+        let mut slits = world.resource_mut::<Slits>();
+        let (count, slits_index) = (slits.count, slits.count / 2);
+        slits.state = SlitState::Stand(slits_index);
+
+        for index in 0..count {
+            let slit_block = SlitBlock {
+                width: SLIT_BLOCK_WIDTH,
+                index,
+            };
+            world.spawn((
+                Transform::from_xyz(
+                    slit_block.position(slits_index),
+                    SLIT_POSITION_VERTICAL,
+                    0.0,
+                ),
+                slit_block_body(),
+                PhysicsLayers::BOUNDARY,
+                slit_block,
+            ));
+        }
+    }
+
+    world.spawn((ball_body(), Text::default(), simulation.glyph.clone()));
+}
@@ -0,0 +1,245 @@
+use super::*;
+use crate::utils::data_dir;
+use std::{fs, io};
+
+pub struct SprintPlugin;
+
+impl Plugin for SprintPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Sprint>()
+            .init_resource::<Leaderboard>()
+            .add_startup_system(load_leaderboard)
+            .add_system_set(
+                SystemSet::on_enter(AppState::Sprint)
+                    .with_system(enter_sprint)
+                    .with_system(make_arena)
+                    .with_system(make_ui)
+                    .with_system(make_keyboard)
+                    .with_system(make_sprint_clock)
+                    .with_system(make_player)
+                    .with_system(make_enemy)
+                    .with_system(make_ball),
+            )
+            .add_system_set(
+                endless_systems()
+                    .with_run_criteria(State::on_update(AppState::Sprint))
+                    .with_system(escape_system)
+                    .with_system(sprint_clock)
+                    .with_system(sprint_system),
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Sprint)
+                    .with_system(cleanup_system::<Cleanup>)
+                    .with_system(clear_glyph_queue),
+            );
+    }
+}
+
+/// A round that ends after a fixed duration and is scored by typing speed.
+#[derive(Resource)]
+pub struct Sprint {
+    /// Duration in seconds, one of [`SPRINT_DURATIONS`].
+    pub duration: u32,
+    pub result: Option<SprintResult>,
+}
+
+impl Default for Sprint {
+    fn default() -> Self {
+        Self {
+            duration: SPRINT_DURATIONS[0],
+            result: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct SprintResult {
+    /// Characters correctly typed per minute.
+    pub speed: f32,
+    /// 1-based rank among runs of the same scheme and duration.
+    pub rank: usize,
+}
+
+#[derive(Clone)]
+pub struct LeaderboardEntry {
+    pub scheme: String,
+    pub duration: u32,
+    pub speed: f32,
+}
+
+/// Best sprint runs, kept per scheme and duration.
+#[derive(Default, Resource)]
+pub struct Leaderboard {
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    /// Runs of a scheme and duration, fastest first.
+    pub fn ranking<'a>(
+        &'a self,
+        scheme: &'a str,
+        duration: u32,
+    ) -> impl Iterator<Item = &'a LeaderboardEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.scheme == scheme && entry.duration == duration)
+            .sorted_by(|a, b| b.speed.total_cmp(&a.speed))
+    }
+
+    /// Adds a run and returns its rank, keeping the [`LEADERBOARD_SIZE`] best runs of each kind.
+    pub fn insert(&mut self, entry: LeaderboardEntry) -> usize {
+        let rank = 1 + self
+            .ranking(&entry.scheme, entry.duration)
+            .filter(|other| other.speed >= entry.speed)
+            .count();
+
+        let kept = self
+            .ranking(&entry.scheme, entry.duration)
+            .chain(std::iter::once(&entry))
+            .sorted_by(|a, b| b.speed.total_cmp(&a.speed))
+            .take(LEADERBOARD_SIZE)
+            .cloned()
+            .collect_vec();
+        self.entries
+            .retain(|other| other.scheme != entry.scheme || other.duration != entry.duration);
+        self.entries.extend(kept);
+
+        rank
+    }
+
+    fn parse(text: &str) -> Self {
+        let entries = text
+            .lines()
+            .filter_map(|line| {
+                let (scheme, duration, speed) = line.split('\t').collect_tuple()?;
+                Some(LeaderboardEntry {
+                    scheme: scheme.into(),
+                    duration: duration.parse().ok()?,
+                    speed: speed.parse().ok()?,
+                })
+            })
+            .collect();
+        Self { entries }
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let dir = data_dir().ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+        let contents = self
+            .entries
+            .iter()
+            .map(|entry| format!("{}\t{}\t{}\n", entry.scheme, entry.duration, entry.speed))
+            .collect::<String>();
+
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(LEADERBOARD_FILE), contents)
+    }
+}
+
+fn load_leaderboard(mut leaderboard: ResMut<Leaderboard>) {
+    if let Some(path) = data_dir().map(|dir| dir.join(LEADERBOARD_FILE)) {
+        if let Ok(text) = fs::read_to_string(path) {
+            *leaderboard = Leaderboard::parse(&text);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn enter_sprint(
+    mut music_track: ResMut<MusicTrack>,
+    time: Res<Time>,
+    mut time_scale: ResMut<TimeScale>,
+    mut score: ResMut<Score>,
+    mut review: ResMut<Review>,
+    mut finger_stats: ResMut<FingerStats>,
+    mut last_keystroke: ResMut<LastKeystroke>,
+    mut sprint: ResMut<Sprint>,
+    mut heal_events: EventWriter<HealEvent>,
+) {
+    // clear score state
+    score.timestamp = time.elapsed_seconds();
+    score.hits = 0;
+    score.miss = 0;
+    score.answers = 0;
+    review.mistakes.clear();
+    *finger_stats = FingerStats::default();
+    last_keystroke.0 = None;
+    sprint.result = None;
+
+    time_scale.reset();
+
+    heal_events.send(HealEvent(Heal::default()));
+
+    if music_track.0 != GAME_MUSIC {
+        music_track.0 = GAME_MUSIC;
+    }
+}
+
+#[derive(Component)]
+struct SprintClock;
+
+fn make_sprint_clock(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    right: Val::Px(16.0),
+                    bottom: Val::Px(16.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load(FONT_FIRA_MONO),
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                },
+            ),
+            ..Default::default()
+        },
+        SprintClock,
+        Cleanup,
+    ));
+}
+
+fn sprint_clock(
+    time: Res<Time>,
+    score: Res<Score>,
+    sprint: Res<Sprint>,
+    mut query: Query<&mut Text, With<SprintClock>>,
+) {
+    let remaining = sprint.duration as f32 - (time.elapsed_seconds() - score.timestamp);
+    for mut text in query.iter_mut() {
+        text.sections[0].value = format!("{:.0}", remaining.max(0.0).ceil());
+    }
+}
+
+/// Ends the sprint after its duration and ranks the typing speed on the [`Leaderboard`].
+fn sprint_system(
+    time: Res<Time>,
+    score: Res<Score>,
+    scheme: ActiveScheme,
+    mut sprint: ResMut<Sprint>,
+    mut leaderboard: ResMut<Leaderboard>,
+    mut app_state: ResMut<State<AppState>>,
+) {
+    let duration = sprint.duration as f32;
+    if sprint.result.is_some() || time.elapsed_seconds() - score.timestamp < duration {
+        return;
+    }
+
+    let speed = score.answers as f32 * 60.0 / duration;
+    let rank = leaderboard.insert(LeaderboardEntry {
+        scheme: scheme.name().into(),
+        duration: sprint.duration,
+        speed,
+    });
+    if let Err(error) = leaderboard.save() {
+        warn!("Failed to save the leaderboard: {error}");
+    }
+
+    sprint.result = Some(SprintResult { speed, rank });
+    let _ = app_state.set(AppState::Score);
+}
@@ -0,0 +1,424 @@
+use super::{
+    deck::DeckFormat,
+    glyph::{is_han, Glyph, GlyphTable},
+    rime::RimeFile,
+};
+use crate::{constants::*, utils::data_dir};
+use bevy::{prelude::*, window::FileDragAndDrop};
+use itertools::Itertools;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The personal practice list, imported by dropping a file onto the window.
+/// Its words are glyphs of one or more characters, typed with the codes of each in turn.
+#[derive(Default, Resource)]
+pub struct WordList {
+    pub words: Vec<Glyph>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WordListFormat {
+    /// Plain text, split into words at spaces, punctuation and anything other than Chinese characters,
+    /// whose codes are looked up in the [`GlyphTable`]. Runs longer than [`WORD_MAX_LENGTH`] are prose
+    /// and split into characters.
+    Text,
+    /// One entry per line: text, space-separated codes and an optional reading.
+    Tsv,
+    /// Same columns as [`WordListFormat::Tsv`], separated by commas.
+    Csv,
+}
+
+impl WordListFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "txt" => Some(Self::Text),
+            "tsv" => Some(Self::Tsv),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ParsedWordList {
+    pub glyphs: Vec<Glyph>,
+    /// Entries without explicit codes that aren't in the [`GlyphTable`] either.
+    pub unknown: Vec<String>,
+}
+
+/// Splits a line into fields, which may be quoted to hold the separator, with `""` for a quote.
+pub fn split_fields(line: &str, separator: char) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            _ if char == separator && !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(char),
+        }
+    }
+    fields.push(field);
+    fields
+        .into_iter()
+        .map(|field| field.trim().into())
+        .collect()
+}
+
+pub fn parse_word_list(text: &str, format: WordListFormat, table: &GlyphTable) -> ParsedWordList {
+    let mut glyphs = vec![];
+    let mut unknown = vec![];
+
+    let text = text.trim_start_matches('\u{feff}');
+    let separator = match format {
+        WordListFormat::Text => {
+            // only Chinese characters are practised, the rest separates words
+            for run in text
+                .split(|c: char| !is_han(c))
+                .filter(|run| !run.is_empty())
+            {
+                if run.chars().count() > WORD_MAX_LENGTH {
+                    for glyph in table.read(run) {
+                        match glyph {
+                            Ok(glyph) => glyphs.push(glyph),
+                            Err(character) => unknown.push(character.to_string()),
+                        }
+                    }
+                    continue;
+                }
+                match table.word(run) {
+                    Ok(glyph) => glyphs.push(glyph),
+                    Err(_) => unknown.push(run.to_string()),
+                }
+            }
+            None
+        }
+        WordListFormat::Tsv => Some('\t'),
+        WordListFormat::Csv => Some(','),
+    };
+
+    if let Some(separator) = separator {
+        for line in text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let columns = split_fields(line, separator);
+            let entry = columns.first().map(String::as_str).unwrap_or_default();
+            let codes = columns
+                .get(1)
+                .map(|codes| codes.split_whitespace().collect_vec())
+                .unwrap_or_default();
+
+            if entry.is_empty() {
+                continue;
+            }
+            if codes.is_empty() {
+                match table.word(entry) {
+                    Ok(glyph) => glyphs.push(glyph),
+                    Err(_) => unknown.push(entry.to_string()),
+                }
+            } else {
+                let reading = columns
+                    .get(2)
+                    .filter(|reading| !reading.is_empty())
+                    .cloned()
+                    .or_else(|| table.word(entry).ok().map(|glyph| glyph.reading))
+                    .unwrap_or_default();
+                glyphs.push(Glyph::new(entry, &reading, &codes));
+            }
+        }
+    }
+
+    ParsedWordList {
+        glyphs: glyphs
+            .into_iter()
+            .unique_by(|glyph| glyph.character.clone())
+            .collect(),
+        unknown: unknown.into_iter().unique().collect(),
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum ImportFormat {
+    Deck(DeckFormat),
+    WordList(WordListFormat),
+}
+
+/// A file dropped onto the window, read once for the importer of its format.
+/// Files that can't be read are reported by [`import_word_list`].
+pub struct DroppedFile {
+    pub path: PathBuf,
+    pub read: Result<(ImportFormat, String), String>,
+}
+
+fn read_dropped_file(path: &Path) -> Result<(ImportFormat, String), String> {
+    let word_list = WordListFormat::from_path(path);
+    if word_list.is_none() && !DeckFormat::may_be(path) {
+        return Err("Expected a .txt, .tsv or .csv file".into());
+    }
+    let bytes = fs::read(path).map_err(|error| error.to_string())?;
+    let text = String::from_utf8(bytes).map_err(|_| "The file is not UTF-8 encoded")?;
+    let format = match (DeckFormat::detect(path, &text), word_list) {
+        (Some(format), _) => ImportFormat::Deck(format),
+        (None, Some(format)) => ImportFormat::WordList(format),
+        (None, None) => return Err("Expected a .txt, .tsv or .csv file".into()),
+    };
+    Ok((format, text))
+}
+
+/// Reads the files dropped onto the window, besides the Rime ones [`import_rime`](super::rime::import_rime) reads itself.
+pub fn read_dropped_files(
+    mut events: EventReader<FileDragAndDrop>,
+    mut dropped_files: EventWriter<DroppedFile>,
+) {
+    for event in events.iter() {
+        let path = match event {
+            FileDragAndDrop::DroppedFile { path_buf, .. } => path_buf,
+            _ => continue,
+        };
+        if RimeFile::from_path(path).is_some() {
+            continue;
+        }
+        dropped_files.send(DroppedFile {
+            path: path.clone(),
+            read: read_dropped_file(path),
+        });
+    }
+}
+
+/// Formats glyphs as a [`WordListFormat::Tsv`] word list, with their codes and reading.
+pub fn word_list_tsv(glyphs: &[Glyph]) -> String {
+    glyphs
+        .iter()
+        .map(|glyph| {
+            format!(
+                "{}\t{}\t{}\n",
+                glyph.character,
+                glyph.codes.join(" "),
+                glyph.reading
+            )
+        })
+        .collect()
+}
+
+fn write_word_list(word_list: &WordList) -> io::Result<()> {
+    let dir = data_dir().ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(WORD_LIST_FILE), word_list_tsv(&word_list.words))
+}
+
+/// Restores the word list saved by an earlier session.
+pub fn load_word_list(table: Res<GlyphTable>, mut word_list: ResMut<WordList>) {
+    if let Some(path) = data_dir().map(|dir| dir.join(WORD_LIST_FILE)) {
+        if let Ok(text) = fs::read_to_string(path) {
+            word_list.words = parse_word_list(&text, WordListFormat::Tsv, &table).glyphs;
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ImportPanel(Timer);
+
+/// Imports a word list dropped onto the window and reports the result in an [`ImportPanel`].
+pub fn import_word_list(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    table: Res<GlyphTable>,
+    mut word_list: ResMut<WordList>,
+    mut events: EventReader<DroppedFile>,
+    panels: Query<Entity, With<ImportPanel>>,
+) {
+    for event in events.iter() {
+        let parsed = match &event.read {
+            Ok((ImportFormat::WordList(format), text)) => {
+                Ok(parse_word_list(text, *format, &table))
+            }
+            // imported by import_deck
+            Ok((ImportFormat::Deck(_), _)) => continue,
+            Err(error) => Err(error.clone()),
+        };
+        let name = event.path.file_name().unwrap_or_default().to_string_lossy();
+
+        let (summary, unknown) = match parsed {
+            Ok(parsed) if parsed.glyphs.is_empty() => {
+                (format!("No known characters in {name}"), parsed.unknown)
+            }
+            Ok(parsed) => {
+                word_list.words = parsed.glyphs;
+                if let Err(error) = write_word_list(&word_list) {
+                    warn!("Failed to save the word list: {error}");
+                }
+                (
+                    format!("Imported {} words from {name}", word_list.words.len()),
+                    parsed.unknown,
+                )
+            }
+            Err(error) => (error, vec![]),
+        };
+
+        let unknown = if unknown.is_empty() {
+            String::new()
+        } else {
+            let more = if unknown.len() > IMPORT_PANEL_MAX_UNKNOWN {
+                " ..."
+            } else {
+                ""
+            };
+            let shown = unknown.iter().take(IMPORT_PANEL_MAX_UNKNOWN).join(" ");
+            format!("\nUnknown: {shown}{more}")
+        };
+
+        spawn_import_panel(&mut commands, &asset_server, &panels, summary, unknown);
+    }
+}
+
+/// Reports an import, replacing the previous report.
+pub fn spawn_import_panel(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    panels: &Query<Entity, With<ImportPanel>>,
+    summary: String,
+    details: String,
+) {
+    for entity in panels.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let style = TextStyle {
+        font: asset_server.load(FONT_LXGW),
+        font_size: 16.0,
+        color: Color::WHITE,
+    };
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Px(16.0),
+                        top: Val::Px(16.0),
+                        ..Default::default()
+                    },
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+                ..Default::default()
+            },
+            ImportPanel(Timer::from_seconds(IMPORT_PANEL_DURATION, TimerMode::Once)),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text::from_sections([
+                    TextSection::new(summary, style.clone()),
+                    TextSection::new(
+                        details,
+                        TextStyle {
+                            color: Color::GOLD,
+                            ..style
+                        },
+                    ),
+                ]),
+                ..Default::default()
+            });
+        });
+}
+
+pub fn import_panel(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ImportPanel)>,
+) {
+    for (entity, mut panel) in query.iter_mut() {
+        if panel.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(parsed: &ParsedWordList) -> Vec<(&str, &str, &str)> {
+        parsed
+            .glyphs
+            .iter()
+            .map(|glyph| {
+                let code = glyph.codes.first().map_or("", String::as_str);
+                (glyph.character.as_str(), code, glyph.reading.as_str())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn quoted_fields_hold_the_separator_and_quotes() {
+        assert_eq!(split_fields("a, b ,c", ','), ["a", "b", "c"]);
+        assert_eq!(
+            split_fields(r#""a, b", "say ""hi""",c"#, ','),
+            ["a, b", r#"say "hi""#, "c"]
+        );
+        assert_eq!(
+            split_fields("中\t\"zhong\tzhong\"", '\t'),
+            ["中", "zhong\tzhong"]
+        );
+        // a quote inside a field is kept as it is
+        assert_eq!(split_fields(r#"5" screen,x"#, ','), [r#"5" screen"#, "x"]);
+        assert_eq!(split_fields("a,", ','), ["a", ""]);
+    }
+
+    #[test]
+    fn plain_text_splits_into_words_of_chinese_characters() {
+        let table = GlyphTable::default();
+        let parsed = parse_word_list(
+            "\u{feff}中国, hello 你好！中国",
+            WordListFormat::Text,
+            &table,
+        );
+        assert_eq!(
+            entries(&parsed),
+            [
+                ("中国", "zhongguo", "zhong1 guo2"),
+                ("你好", "nihao", "ni3 hao3")
+            ]
+        );
+        assert!(parsed.unknown.is_empty());
+
+        // a run longer than a word is prose, read a character at a time
+        let parsed = parse_word_list("我是中国的学生", WordListFormat::Text, &table);
+        assert_eq!(parsed.glyphs.len(), 7);
+    }
+
+    #[test]
+    fn separated_columns_give_codes_and_readings() {
+        let table = GlyphTable::default();
+        let text = "# comment\n中\tzhong zong\n银行\tyinhang\tyin2 hang2\n你好\n\n\t\n𪚥\n";
+        let parsed = parse_word_list(text, WordListFormat::Tsv, &table);
+        assert_eq!(
+            entries(&parsed),
+            [
+                ("中", "zhong", "zhong1"),
+                ("银行", "yinhang", "yin2 hang2"),
+                ("你好", "nihao", "ni3 hao3"),
+            ]
+        );
+        assert_eq!(parsed.glyphs[0].codes, ["zhong", "zong"]);
+        assert_eq!(parsed.unknown, ["𪚥"]);
+
+        let parsed = parse_word_list("\"好\",hao\n", WordListFormat::Csv, &table);
+        assert_eq!(entries(&parsed), [("好", "hao", "hao3")]);
+    }
+}
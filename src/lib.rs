@@ -1,4 +1,5 @@
 use bevy::{prelude::*, render::texture::ImageSampler, window::CursorGrabMode};
+#[cfg(feature = "audio")]
 use bevy_kira_audio::AudioPlugin;
 use wasm_bindgen::prelude::*;
 
@@ -8,8 +9,19 @@ mod effects;
 mod game;
 mod loading;
 mod menu;
+#[cfg(feature = "audio")]
+mod music;
 mod utils;
 
+// physics is public for the benchmarks
+pub use game::{
+    physics_systems, CollisionEvent, ContactEvent, ContactPhase, Force, ForceField, Motion,
+    PhysicsLayers, RigidBody,
+};
+// headless runs of the game logic, for regression tests
+pub use game::{simulate, Glyph, Score, Simulation, SimulationMode, SimulationResult};
+pub use utils::Shape;
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum AppState {
     Loading,
@@ -17,7 +29,10 @@ pub enum AppState {
     Settings,
     Battle,
     Practice,
+    Sprint,
     Score,
+    Review,
+    Stats,
 }
 
 #[derive(Resource)]
@@ -39,6 +54,7 @@ impl TimeScale {
 pub struct AudioVolume {
     pub music: f32,
     pub effects: f32,
+    pub pronunciation: f32,
 }
 
 #[derive(Resource)]
@@ -94,6 +110,7 @@ pub fn run() {
         .insert_resource(AudioVolume {
             music: 0.3,
             effects: 1.0,
+            pronunciation: 1.0,
         })
         .insert_resource(MusicTrack(""));
 
@@ -118,8 +135,10 @@ pub fn run() {
     #[cfg(not(feature = "dot"))]
     app.add_plugins(default_plugins);
 
-    app.add_plugin(AudioPlugin)
-        .add_state(AppState::Loading)
+    #[cfg(feature = "audio")]
+    app.add_plugin(AudioPlugin).add_plugin(music::MusicPlugin);
+
+    app.add_state(AppState::Loading)
         .add_startup_system(setup)
         .add_system(lock_release_cursor)
         .add_system(color_text_system)
@@ -147,7 +166,7 @@ fn setup(mut commands: Commands) {
 fn lock_release_cursor(app_state: Res<State<AppState>>, mut windows: ResMut<Windows>) {
     if let Some(window) = windows.get_primary_mut() {
         match app_state.current() {
-            AppState::Battle | AppState::Practice => {
+            AppState::Battle | AppState::Practice | AppState::Sprint => {
                 if cfg!(any(target_arch = "wasm32", target_os = "macos")) {
                     window.set_cursor_grab_mode(CursorGrabMode::Locked);
                 } else {
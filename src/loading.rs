@@ -41,20 +41,24 @@ fn setup(server: Res<AssetServer>, mut loading: ResMut<AssetsLoading>) {
     loading.push(server.load_untyped(WRENCH_ICON));
     loading.push(server.load_untyped(EXIT_ICON));
 
-    loading.push(server.load_untyped(HIT_AUDIO));
-    loading.push(server.load_untyped(MISS_AUDIO));
-    loading.push(server.load_untyped(EXPLOSION_AUDIO));
-    loading.push(server.load_untyped(LOSE_AUDIO));
+    // without audio there is no loader for the sounds
+    #[cfg(feature = "audio")]
+    {
+        loading.push(server.load_untyped(HIT_AUDIO));
+        loading.push(server.load_untyped(MISS_AUDIO));
+        loading.push(server.load_untyped(EXPLOSION_AUDIO));
+        loading.push(server.load_untyped(LOSE_AUDIO));
 
-    for audio in IMPACT_AUDIOS {
-        loading.push(server.load_untyped(audio));
-    }
+        for audio in IMPACT_AUDIOS {
+            loading.push(server.load_untyped(audio));
+        }
 
-    loading.push(server.load_untyped(BUTTON_HOVER_AUDIO));
-    loading.push(server.load_untyped(BUTTON_CLICK_AUDIO));
+        loading.push(server.load_untyped(BUTTON_HOVER_AUDIO));
+        loading.push(server.load_untyped(BUTTON_CLICK_AUDIO));
 
-    loading.push(server.load_untyped(MENU_MUSIC));
-    loading.push(server.load_untyped(GAME_MUSIC));
+        loading.push(server.load_untyped(MENU_MUSIC));
+        loading.push(server.load_untyped(GAME_MUSIC));
+    }
 }
 
 fn check_assets_loaded(
@@ -1,21 +1,29 @@
 use crate::{
     constants::*,
-    game::Score,
+    game::{
+        mix_punctuation, punctuation_glyphs, sentences, ActiveScheme, CharacterSet, Decks, Finger,
+        FingerGuide, FingerStats, FuzzyPinyin, FuzzyRule, GlossDisplay, GlyphQueue, GlyphTable,
+        KeyLayout, KeyStats, KeyboardOverlay, Leaderboard, PunctuationMix, Review, Scheme, Score,
+        Sentence, Sprint, TypingMode, WordList,
+    },
     utils::{cleanup_system, escape_system},
     AppState, AudioVolume, ColorText, HintText, MusicTrack, TimeScale,
 };
 use bevy::prelude::*;
-use bevy_kira_audio::{Audio, AudioApp, AudioChannel, AudioControl};
+#[cfg(feature = "audio")]
+use bevy_kira_audio::{AudioApp, AudioChannel, AudioControl};
+use itertools::Itertools;
 
 pub struct MenuPlugin;
 
+#[cfg(feature = "audio")]
 #[derive(Resource)]
 struct ButtonAudio;
 
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ButtonStyle>()
-            .add_audio_channel::<ButtonAudio>()
+            .init_resource::<ReviewSelection>()
             .add_system_set(
                 SystemSet::new()
                     .label(ButtonSystems)
@@ -24,7 +32,6 @@ impl Plugin for MenuPlugin {
                     .with_system(value_system)
                     .with_system(value_action),
             )
-            .add_system(button_audio.after(ButtonSystems))
             .add_system_set(
                 SystemSet::on_enter(AppState::Menu)
                     .with_system(enter_menu)
@@ -45,7 +52,29 @@ impl Plugin for MenuPlugin {
             )
             .add_system_set(
                 SystemSet::on_exit(AppState::Score).with_system(cleanup_system::<Cleanup>),
+            )
+            .add_system_set(SystemSet::on_enter(AppState::Review).with_system(make_review))
+            .add_system_set(
+                SystemSet::on_update(AppState::Review)
+                    .with_system(escape_system)
+                    .with_system(review_navigation),
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Review).with_system(cleanup_system::<Cleanup>),
+            )
+            .add_system_set(SystemSet::on_enter(AppState::Stats).with_system(make_stats))
+            .add_system_set(
+                SystemSet::on_update(AppState::Stats)
+                    .with_system(escape_system)
+                    .with_system(export_stats),
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Stats).with_system(cleanup_system::<Cleanup>),
             );
+
+        #[cfg(feature = "audio")]
+        app.add_audio_channel::<ButtonAudio>()
+            .add_system(button_audio.after(ButtonSystems));
     }
 }
 
@@ -59,14 +88,39 @@ pub struct ButtonSystems;
 enum ButtonAction {
     Play,
     Tutorial,
+    Dictation,
+    Sentence,
+    Punctuation,
+    Sprint,
+    Stats,
     Settings,
+    Review,
+    Drill,
+    Export,
     Back,
 }
 
+#[derive(Component)]
+struct ReviewRow(usize);
+
+#[derive(Default, Resource)]
+struct ReviewSelection(usize);
+
 #[derive(Clone, Copy, Component)]
 enum ValueAction {
     AudioVolume(f32),
     MusicVolume(f32),
+    PronunciationVolume(f32),
+    SprintDuration(u32),
+    Scheme(Scheme),
+    Keyboard(bool),
+    KeyLayout(KeyLayout),
+    PunctuationMix(bool),
+    FingerGuide(bool),
+    GlossDisplay(bool),
+    CharacterSet(CharacterSet),
+    /// Toggles a fuzzy pinyin rule.
+    FuzzyRule(FuzzyRule),
 }
 
 #[derive(Resource)]
@@ -115,20 +169,9 @@ impl FromWorld for ButtonStyle {
     }
 }
 
-fn enter_menu(
-    mut time_scale: ResMut<TimeScale>,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    volume: Res<AudioVolume>,
-    mut music_track: ResMut<MusicTrack>,
-) {
+fn enter_menu(mut time_scale: ResMut<TimeScale>, mut music_track: ResMut<MusicTrack>) {
     time_scale.reset();
     if music_track.0 != MENU_MUSIC {
-        audio.stop();
-        audio.set_playback_rate(1.0);
-        audio.set_volume(volume.music.into());
-        audio.play(asset_server.load(MENU_MUSIC)).looped();
-
         music_track.0 = MENU_MUSIC;
     }
 }
@@ -251,6 +294,106 @@ fn make_menu(
                         ..Default::default()
                     });
                 });
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Dictation,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(asset_server.load(HELP_ICON)),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Dictation", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Sentence,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(asset_server.load(RIGHT_ICON)),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Sentence", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Punctuation,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(asset_server.load(HELP_ICON)),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Punctuation", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Sprint,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(asset_server.load(RIGHT_ICON)),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Sprint", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Stats,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(asset_server.load(RETICLE_ICON)),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Stats", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
             parent
                 .spawn((
                     ButtonBundle {
@@ -278,6 +421,7 @@ fn make_settings(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     button_style: Res<ButtonStyle>,
+    decks: Res<Decks>,
 ) {
     commands
         .spawn((
@@ -321,121 +465,63 @@ fn make_settings(
                 ..Default::default()
             });
 
-            // audio volume
-            parent
-                .spawn(NodeBundle {
-                    style: Style {
-                        size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
-                        align_items: AlignItems::Center,
-                        ..Default::default()
-                    },
-                    background_color: Color::NONE.into(),
-                    ..Default::default()
-                })
-                .with_children(|parent| {
-                    parent.spawn(TextBundle {
-                        style: Style {
-                            position: UiRect {
-                                left: Val::Percent(10.0),
-                                ..Default::default()
-                            },
-                            margin: UiRect {
-                                right: Val::Percent(10.0),
-                                ..Default::default()
-                            },
-                            ..Default::default()
-                        },
-                        text: Text::from_section(
-                            "Audio",
-                            TextStyle {
-                                font: asset_server.load(FONT_KARMATIC),
-                                font_size: 20.0,
-                                color: Color::WHITE,
-                            },
-                        )
-                        .with_alignment(TextAlignment {
-                            horizontal: HorizontalAlign::Center,
-                            ..Default::default()
-                        }),
-                        ..Default::default()
-                    });
-                    for volume_setting in 0..=10 {
-                        parent.spawn((
-                            ButtonBundle {
-                                style: Style {
-                                    size: Size::new(Val::Px(20.0), Val::Px(20.0)),
-                                    margin: UiRect {
-                                        left: Val::Px(2.0),
-                                        right: Val::Px(2.0),
-                                        ..Default::default()
-                                    },
-                                    ..button_style.button.clone()
-                                },
-                                background_color: SETTING_NORMAL_COLOR.into(),
-                                ..Default::default()
-                            },
-                            ValueAction::AudioVolume(volume_setting as f32 / 10.0),
-                        ));
-                    }
-                });
+            let volumes = |action: fn(f32) -> ValueAction| {
+                (0..=10).map(move |setting| (action(setting as f32 / 10.0), String::new()))
+            };
+            let switch = |action: fn(bool) -> ValueAction| {
+                [(false, "off"), (true, "on")].map(|(enabled, name)| (action(enabled), name.into()))
+            };
+            let mut row = |label, width, values: Vec<(ValueAction, String)>| {
+                settings_row(parent, &asset_server, &button_style, label, width, values)
+            };
 
-            // music volume
-            parent
-                .spawn(NodeBundle {
-                    style: Style {
-                        size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
-                        align_items: AlignItems::Center,
-                        ..Default::default()
-                    },
-                    background_color: Color::NONE.into(),
-                    ..Default::default()
-                })
-                .with_children(|parent| {
-                    parent.spawn(TextBundle {
-                        style: Style {
-                            position: UiRect {
-                                left: Val::Percent(10.0),
-                                ..Default::default()
-                            },
-                            margin: UiRect {
-                                right: Val::Percent(10.0),
-                                ..Default::default()
-                            },
-                            ..Default::default()
-                        },
-                        text: Text::from_section(
-                            "Music",
-                            TextStyle {
-                                font: asset_server.load(FONT_KARMATIC),
-                                font_size: 20.0,
-                                color: Color::WHITE,
-                            },
-                        )
-                        .with_alignment(TextAlignment {
-                            horizontal: HorizontalAlign::Center,
-                            ..Default::default()
-                        }),
-                        ..Default::default()
-                    });
-                    for volume_setting in 0..=10 {
-                        parent.spawn((
-                            ButtonBundle {
-                                style: Style {
-                                    size: Size::new(Val::Px(20.0), Val::Px(20.0)),
-                                    margin: UiRect {
-                                        left: Val::Px(2.0),
-                                        right: Val::Px(2.0),
-                                        ..Default::default()
-                                    },
-                                    ..button_style.button.clone()
-                                },
-                                background_color: SETTING_NORMAL_COLOR.into(),
-                                ..Default::default()
-                            },
-                            ValueAction::MusicVolume(volume_setting as f32 / 10.0),
-                        ));
-                    }
-                });
+            row("Audio", 20.0, volumes(ValueAction::AudioVolume).collect());
+            row("Music", 20.0, volumes(ValueAction::MusicVolume).collect());
+            row(
+                "Voice",
+                20.0,
+                volumes(ValueAction::PronunciationVolume).collect(),
+            );
+            row(
+                "Sprint",
+                50.0,
+                SPRINT_DURATIONS
+                    .map(|duration| (ValueAction::SprintDuration(duration), duration.to_string()))
+                    .into(),
+            );
+            row(
+                "Set",
+                70.0,
+                CharacterSet::options(&decks)
+                    .into_iter()
+                    .map(|set| (ValueAction::CharacterSet(set), set.label(&decks)))
+                    .collect(),
+            );
+            row(
+                "Scheme",
+                70.0,
+                Scheme::ALL
+                    .map(|scheme| (ValueAction::Scheme(scheme), scheme.name().into()))
+                    .into(),
+            );
+            row(
+                "Fuzzy",
+                60.0,
+                FuzzyRule::ALL
+                    .map(|rule| (ValueAction::FuzzyRule(rule), rule.label().into()))
+                    .into(),
+            );
+            row("Keys", 50.0, switch(ValueAction::Keyboard).into());
+            row(
+                "Input",
+                70.0,
+                KeyLayout::ALL
+                    .map(|layout| (ValueAction::KeyLayout(layout), layout.name().into()))
+                    .into(),
+            );
+            row("Gloss", 50.0, switch(ValueAction::GlossDisplay).into());
+            row("Finger", 50.0, switch(ValueAction::FingerGuide).into());
+            row("Punct", 50.0, switch(ValueAction::PunctuationMix).into());
 
             parent
                 .spawn((
@@ -460,17 +546,110 @@ fn make_settings(
         });
 }
 
+/// Spawns a row of settings: a label, then a button for each value, with its text unless empty.
+fn settings_row(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    button_style: &ButtonStyle,
+    label: &str,
+    width: f32,
+    values: Vec<(ValueAction, String)>,
+) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::NONE.into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style {
+                    position: UiRect {
+                        left: Val::Percent(10.0),
+                        ..Default::default()
+                    },
+                    margin: UiRect {
+                        right: Val::Percent(10.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                text: Text::from_section(
+                    label,
+                    TextStyle {
+                        font: asset_server.load(FONT_KARMATIC),
+                        font_size: 20.0,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_alignment(TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            });
+            for (action, text) in values {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(width), Val::Px(20.0)),
+                                margin: UiRect {
+                                    left: Val::Px(2.0),
+                                    right: Val::Px(2.0),
+                                    ..Default::default()
+                                },
+                                ..button_style.button.clone()
+                            },
+                            background_color: SETTING_NORMAL_COLOR.into(),
+                            ..Default::default()
+                        },
+                        action,
+                    ))
+                    .with_children(|parent| {
+                        if !text.is_empty() {
+                            parent.spawn(TextBundle {
+                                text: Text::from_section(
+                                    text,
+                                    TextStyle {
+                                        font: asset_server.load(FONT_FIRA_MONO),
+                                        font_size: 14.0,
+                                        color: SETTING_TEXT_COLOR,
+                                    },
+                                ),
+                                ..Default::default()
+                            });
+                        }
+                    });
+            }
+        });
+}
+
 fn enter_score(mut time_scale: ResMut<TimeScale>) {
     time_scale.reset();
 }
 
+#[allow(clippy::too_many_arguments)]
 fn make_score(
     mut commands: Commands,
     time: Res<Time>,
     score: Res<Score>,
+    sprint: Res<Sprint>,
+    scheme: ActiveScheme,
+    leaderboard: Res<Leaderboard>,
+    finger_stats: Res<FingerStats>,
     asset_server: Res<AssetServer>,
     button_style: Res<ButtonStyle>,
 ) {
+    let title = match sprint.result {
+        Some(_) => "Time's Up!",
+        None => "You Win!",
+    };
+
     commands
         .spawn((
             NodeBundle {
@@ -500,7 +679,7 @@ fn make_score(
                         ..Default::default()
                     },
                     text: Text::from_section(
-                        "You Win!",
+                        title,
                         TextStyle {
                             font: asset_server.load(FONT_ARCADE),
                             font_size: 50.0,
@@ -587,7 +766,7 @@ fn make_score(
 
             // player miss
             parent.spawn(TextBundle {
-                style: term_style,
+                style: term_style.clone(),
                 text: Text {
                     sections: vec![
                         TextSection {
@@ -612,37 +791,724 @@ fn make_score(
                 ..Default::default()
             });
 
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: button_style.button.clone(),
-                        background_color: BUTTON_NORMAL_COLOR.into(),
-                        ..Default::default()
-                    },
-                    ButtonAction::Back,
-                ))
-                .with_children(|parent| {
-                    parent.spawn(ImageBundle {
-                        style: button_style.icon.clone(),
-                        image: UiImage(asset_server.load(EXIT_ICON)),
-                        ..Default::default()
-                    });
-                    parent.spawn(TextBundle {
-                        text: Text::from_section("Back", button_style.text.clone()),
-                        ..Default::default()
-                    });
-                });
-        });
-}
-
-#[allow(clippy::type_complexity)]
-fn button_audio(
-    interaction_query: Query<
-        (&Interaction, Option<&ButtonAction>),
-        (Changed<Interaction>, With<Button>),
-    >,
-    audio: Res<AudioChannel<ButtonAudio>>,
-    volume: Res<AudioVolume>,
+            // finger load and same-finger bigrams
+            let finger_style = TextStyle {
+                font: asset_server.load(FONT_FIRA_MONO),
+                font_size: 14.0,
+                color: Color::WHITE,
+            };
+            let load = Finger::ALL
+                .iter()
+                .map(|finger| {
+                    format!(
+                        "{} {:.0}%",
+                        finger.label(),
+                        finger_stats.share(*finger) * 100.0
+                    )
+                })
+                .join("  ");
+            let bigrams = finger_stats
+                .frequent_bigrams()
+                .take(FINGER_BIGRAMS_SHOWN)
+                .map(|((first, second), count)| format!("{first}{second} x{count}"))
+                .join("  ");
+            parent.spawn(TextBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Auto),
+                    ..term_style.clone()
+                },
+                text: Text::from_sections([
+                    TextSection::new(format!("{load}\n"), finger_style.clone()),
+                    TextSection::new(
+                        format!(
+                            "Same finger: {}  {bigrams}",
+                            finger_stats.same_finger_bigrams.values().sum::<u32>()
+                        ),
+                        TextStyle {
+                            color: Color::GOLD,
+                            ..finger_style
+                        },
+                    ),
+                ]),
+                ..Default::default()
+            });
+
+            if let Some(result) = sprint.result {
+                let label_style = TextStyle {
+                    font: asset_server.load(FONT_KARMATIC),
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                };
+                let value_style = TextStyle {
+                    color: Color::GOLD,
+                    ..label_style.clone()
+                };
+
+                // typing speed and rank
+                parent.spawn(TextBundle {
+                    style: term_style.clone(),
+                    text: Text::from_sections([
+                        TextSection::new("Speed: ", label_style.clone()),
+                        TextSection::new(format!("{:.1} cpm", result.speed), value_style.clone()),
+                    ]),
+                    ..Default::default()
+                });
+                parent.spawn(TextBundle {
+                    style: term_style.clone(),
+                    text: Text::from_sections([
+                        TextSection::new("Rank: ", label_style.clone()),
+                        TextSection::new(result.rank.to_string(), value_style.clone()),
+                    ]),
+                    ..Default::default()
+                });
+
+                // best runs of the same scheme and duration
+                let entry_style = TextStyle {
+                    font: asset_server.load(FONT_FIRA_MONO),
+                    font_size: 16.0,
+                    color: Color::WHITE,
+                };
+                for (index, entry) in leaderboard
+                    .ranking(scheme.name(), sprint.duration)
+                    .take(LEADERBOARD_SHOWN)
+                    .enumerate()
+                {
+                    let color = if index + 1 == result.rank {
+                        Color::GOLD
+                    } else {
+                        Color::WHITE
+                    };
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            position: UiRect {
+                                left: Val::Percent(10.0),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            format!("{:>2}. {:>6.1} cpm", index + 1, entry.speed),
+                            TextStyle {
+                                color,
+                                ..entry_style.clone()
+                            },
+                        ),
+                        ..Default::default()
+                    });
+                }
+            }
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Review,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(asset_server.load(HELP_ICON)),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Review", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Back,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(asset_server.load(EXIT_ICON)),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Back", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+        });
+}
+
+fn make_review(
+    mut commands: Commands,
+    review: Res<Review>,
+    scheme: ActiveScheme,
+    mut selection: ResMut<ReviewSelection>,
+    asset_server: Res<AssetServer>,
+    button_style: Res<ButtonStyle>,
+) {
+    selection.0 = 0;
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                background_color: Color::NONE.into(),
+                ..Default::default()
+            },
+            Cleanup,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style {
+                    position: UiRect {
+                        left: Val::Percent(10.0),
+                        ..Default::default()
+                    },
+                    margin: UiRect {
+                        bottom: Val::Percent(10.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                text: Text::from_section(
+                    "Review",
+                    TextStyle {
+                        font: asset_server.load(FONT_KARMATIC),
+                        font_size: 30.0,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_alignment(TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            });
+
+            let term_style = Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(30.0)),
+                position: UiRect {
+                    left: Val::Percent(10.0),
+                    ..Default::default()
+                },
+                margin: UiRect {
+                    top: Val::Px(5.0),
+                    bottom: Val::Px(5.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let text_style = TextStyle {
+                font: asset_server.load(FONT_LXGW),
+                font_size: 20.0,
+                color: Color::WHITE,
+            };
+
+            if review.mistakes.is_empty() {
+                parent.spawn(TextBundle {
+                    style: term_style.clone(),
+                    text: Text::from_section("No mistakes!", text_style.clone()),
+                    ..Default::default()
+                });
+            }
+
+            // character, typed codes, correct codes, attempts
+            for (index, mistake) in review.mistakes.iter().enumerate() {
+                let typed = if mistake.typed.is_empty() {
+                    "-".into()
+                } else {
+                    mistake.typed.join(" ")
+                };
+
+                parent.spawn((
+                    TextBundle {
+                        style: term_style.clone(),
+                        text: Text {
+                            sections: vec![
+                                TextSection {
+                                    value: format!("{}  ", mistake.glyph.character),
+                                    style: TextStyle {
+                                        color: Color::GOLD,
+                                        ..text_style.clone()
+                                    },
+                                },
+                                TextSection {
+                                    value: format!("{typed} -> "),
+                                    style: text_style.clone(),
+                                },
+                                TextSection {
+                                    value: scheme.codes(&mistake.glyph).join(" / "),
+                                    style: text_style.clone(),
+                                },
+                                TextSection {
+                                    value: format!(
+                                        "  x{} ({:.1}s)",
+                                        mistake.attempts, mistake.duration
+                                    ),
+                                    style: text_style.clone(),
+                                },
+                            ],
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    ReviewRow(index),
+                ));
+            }
+
+            if !review.mistakes.is_empty() {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: button_style.button.clone(),
+                            background_color: BUTTON_NORMAL_COLOR.into(),
+                            ..Default::default()
+                        },
+                        ButtonAction::Drill,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(ImageBundle {
+                            style: button_style.icon.clone(),
+                            image: UiImage(asset_server.load(RETICLE_ICON)),
+                            ..Default::default()
+                        });
+                        parent.spawn(TextBundle {
+                            text: Text::from_section("Drill", button_style.text.clone()),
+                            ..Default::default()
+                        });
+                    });
+            }
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Back,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(asset_server.load(EXIT_ICON)),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Back", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+        });
+}
+
+/// Moves the selection with the arrow keys, scrolling the list by [`REVIEW_PAGE_SIZE`] rows.
+/// Pressing enter drills the reviewed glyphs.
+fn review_navigation(
+    keyboard_input: Res<Input<KeyCode>>,
+    review: Res<Review>,
+    mut selection: ResMut<ReviewSelection>,
+    mut queue: ResMut<GlyphQueue>,
+    mut app_state: ResMut<State<AppState>>,
+    mut query: Query<(&ReviewRow, &mut Text, &mut Style)>,
+) {
+    let count = review.mistakes.len();
+    if count == 0 {
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Down) {
+        selection.0 = (selection.0 + 1).min(count - 1);
+    }
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        selection.0 = selection.0.saturating_sub(1);
+    }
+    if keyboard_input.just_pressed(KeyCode::Return) {
+        start_drill(&review, &mut queue);
+        let _ = app_state.set(AppState::Practice);
+        return;
+    }
+
+    let page = selection.0 / REVIEW_PAGE_SIZE;
+    for (row, mut text, mut style) in query.iter_mut() {
+        style.display = if row.0 / REVIEW_PAGE_SIZE == page {
+            Display::Flex
+        } else {
+            Display::None
+        };
+
+        let color = if row.0 == selection.0 {
+            Color::WHITE
+        } else {
+            Color::GRAY
+        };
+        for section in text.sections.iter_mut().skip(1) {
+            section.style.color = color;
+        }
+    }
+}
+
+#[derive(Component)]
+struct StatsStatus;
+
+fn make_stats(
+    mut commands: Commands,
+    stats: Res<KeyStats>,
+    scheme: ActiveScheme,
+    layout: Res<KeyLayout>,
+    asset_server: Res<AssetServer>,
+    button_style: Res<ButtonStyle>,
+) {
+    let stats = stats.get(scheme.name());
+    let key_stat = |key: char| stats.and_then(|stats| stats.keys.get(&key));
+
+    // color keys between the fastest and the slowest average latency
+    let latencies = stats
+        .iter()
+        .flat_map(|stats| stats.keys.values())
+        .filter_map(|stat| stat.average_latency())
+        .collect_vec();
+    let fastest = latencies.iter().copied().fold(f32::INFINITY, f32::min);
+    let slowest = latencies.iter().copied().fold(0.0, f32::max);
+    let heat = |latency: f32| {
+        let t = if slowest > fastest {
+            (latency - fastest) / (slowest - fastest)
+        } else {
+            0.0
+        };
+        let [fast, slow] = KEY_HEAT_COLORS;
+        Color::rgb(
+            fast.r() + (slow.r() - fast.r()) * t,
+            fast.g() + (slow.g() - fast.g()) * t,
+            fast.b() + (slow.b() - fast.b()) * t,
+        )
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                background_color: Color::NONE.into(),
+                ..Default::default()
+            },
+            Cleanup,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style {
+                    position: UiRect {
+                        left: Val::Percent(10.0),
+                        ..Default::default()
+                    },
+                    margin: UiRect {
+                        bottom: Val::Percent(5.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                text: Text::from_section(
+                    format!("Keys ({})", scheme.name()),
+                    TextStyle {
+                        font: asset_server.load(FONT_KARMATIC),
+                        font_size: 30.0,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_alignment(TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            });
+
+            let letter_style = TextStyle {
+                font: asset_server.load(FONT_FIRA_MONO),
+                font_size: 16.0,
+                color: Color::WHITE,
+            };
+            let number_style = TextStyle {
+                font: asset_server.load(FONT_FIRA_MONO),
+                font_size: 10.0,
+                color: Color::WHITE,
+            };
+
+            // heatmap keyboard, latency in ms and error rate on each key
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    background_color: Color::NONE.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for row in KEYBOARD_ROWS {
+                        parent
+                            .spawn(NodeBundle {
+                                background_color: Color::NONE.into(),
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                for position in row.chars() {
+                                    let key = layout.char_at(position).unwrap_or(position);
+                                    let stat = key_stat(key);
+                                    let color = stat
+                                        .and_then(|stat| stat.average_latency())
+                                        .map_or(KEYBOARD_KEY_COLOR, heat);
+                                    let numbers = stat
+                                        .map(|stat| {
+                                            format!(
+                                                "{:.0}\n{:.0}%",
+                                                stat.average_latency().unwrap_or_default() * 1000.0,
+                                                stat.error_rate().unwrap_or_default() * 100.0
+                                            )
+                                        })
+                                        .unwrap_or_default();
+
+                                    parent
+                                        .spawn(NodeBundle {
+                                            style: Style {
+                                                size: Size::new(Val::Px(48.0), Val::Px(48.0)),
+                                                margin: UiRect::all(Val::Px(2.0)),
+                                                flex_direction: FlexDirection::Column,
+                                                justify_content: JustifyContent::Center,
+                                                align_items: AlignItems::Center,
+                                                ..Default::default()
+                                            },
+                                            background_color: color.into(),
+                                            ..Default::default()
+                                        })
+                                        .with_children(|parent| {
+                                            parent.spawn(TextBundle::from_section(
+                                                key.to_ascii_uppercase(),
+                                                letter_style.clone(),
+                                            ));
+                                            parent.spawn(TextBundle::from_section(
+                                                numbers,
+                                                number_style.clone(),
+                                            ));
+                                        });
+                                }
+                            });
+                    }
+                });
+
+            let term_style = Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(24.0)),
+                position: UiRect {
+                    left: Val::Percent(10.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let text_style = TextStyle {
+                font: asset_server.load(FONT_FIRA_MONO),
+                font_size: 16.0,
+                color: Color::WHITE,
+            };
+
+            // slowest key pairs
+            parent.spawn(TextBundle {
+                style: Style {
+                    margin: UiRect {
+                        top: Val::Px(20.0),
+                        ..Default::default()
+                    },
+                    ..term_style.clone()
+                },
+                text: Text::from_section("Slow digraphs", text_style.clone()),
+                ..Default::default()
+            });
+            let digraphs = stats
+                .map(|stats| {
+                    stats
+                        .slow_digraphs()
+                        .take(KEY_STATS_SLOW_DIGRAPHS)
+                        .collect_vec()
+                })
+                .unwrap_or_default();
+            if digraphs.is_empty() {
+                parent.spawn(TextBundle {
+                    style: term_style.clone(),
+                    text: Text::from_section("-", text_style.clone()),
+                    ..Default::default()
+                });
+            }
+            for ((first, second), stat) in digraphs {
+                parent.spawn(TextBundle {
+                    style: term_style.clone(),
+                    text: Text::from_sections([
+                        TextSection::new(
+                            format!("{first}->{second} "),
+                            TextStyle {
+                                color: Color::GOLD,
+                                ..text_style.clone()
+                            },
+                        ),
+                        TextSection::new(
+                            format!(
+                                "{:>4.0} ms  x{}",
+                                stat.average_latency() * 1000.0,
+                                stat.count
+                            ),
+                            text_style.clone(),
+                        ),
+                    ]),
+                    ..Default::default()
+                });
+            }
+
+            // answers accepted only thanks to fuzzy pinyin
+            parent.spawn(TextBundle {
+                style: Style {
+                    margin: UiRect {
+                        top: Val::Px(20.0),
+                        ..Default::default()
+                    },
+                    ..term_style.clone()
+                },
+                text: Text::from_section("Fuzzy rules needed", text_style.clone()),
+                ..Default::default()
+            });
+            let fuzzy = stats
+                .map(|stats| {
+                    stats
+                        .fuzzy
+                        .iter()
+                        .sorted()
+                        .map(|(rule, count)| format!("{} x{count}", rule.label()))
+                        .join("  ")
+                })
+                .filter(|fuzzy| !fuzzy.is_empty())
+                .unwrap_or_else(|| "-".into());
+            parent.spawn(TextBundle {
+                style: term_style.clone(),
+                text: Text::from_section(fuzzy, text_style.clone()),
+                ..Default::default()
+            });
+
+            parent.spawn((
+                TextBundle {
+                    style: term_style,
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font_size: 12.0,
+                            ..text_style
+                        },
+                    ),
+                    ..Default::default()
+                },
+                StatsStatus,
+            ));
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Export,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(asset_server.load(RIGHT_ICON)),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Export", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: button_style.button.clone(),
+                        background_color: BUTTON_NORMAL_COLOR.into(),
+                        ..Default::default()
+                    },
+                    ButtonAction::Back,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(ImageBundle {
+                        style: button_style.icon.clone(),
+                        image: UiImage(asset_server.load(EXIT_ICON)),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        text: Text::from_section("Back", button_style.text.clone()),
+                        ..Default::default()
+                    });
+                });
+        });
+}
+
+/// Writes the statistics of the active scheme to a CSV file and shows where.
+#[allow(clippy::type_complexity)]
+fn export_stats(
+    interaction_query: Query<(&Interaction, &ButtonAction), (Changed<Interaction>, With<Button>)>,
+    stats: Res<KeyStats>,
+    scheme: ActiveScheme,
+    mut query: Query<&mut Text, With<StatsStatus>>,
+) {
+    for (interaction, action) in interaction_query.iter() {
+        if *interaction != Interaction::Clicked || !matches!(action, ButtonAction::Export) {
+            continue;
+        }
+
+        let status = match stats.get(scheme.name()) {
+            Some(stats) => match stats.export(scheme.name()) {
+                Ok(path) => format!("Exported to {}", path.display()),
+                Err(error) => format!("Export failed: {error}"),
+            },
+            None => "Nothing typed yet".into(),
+        };
+        for mut text in query.iter_mut() {
+            text.sections[0].value = status.clone();
+        }
+    }
+}
+
+/// Fills the [`GlyphQueue`] with each reviewed glyph once.
+fn start_drill(review: &Review, queue: &mut GlyphQueue) {
+    *queue = GlyphQueue {
+        glyphs: review
+            .mistakes
+            .iter()
+            .map(|mistake| mistake.glyph.clone())
+            .unique_by(|glyph| glyph.character.clone())
+            .collect(),
+        index: 0,
+    };
+}
+
+#[cfg(feature = "audio")]
+#[allow(clippy::type_complexity)]
+fn button_audio(
+    interaction_query: Query<
+        (&Interaction, Option<&ButtonAction>),
+        (Changed<Interaction>, With<Button>),
+    >,
+    audio: Res<AudioChannel<ButtonAudio>>,
+    volume: Res<AudioVolume>,
     asset_server: Res<AssetServer>,
 ) {
     // let channel = AudioChannel::new("button".into());
@@ -696,28 +1562,112 @@ fn button_system(
     }
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn button_action(
     interaction_query: Query<(&Interaction, &ButtonAction), (Changed<Interaction>, With<Button>)>,
     mut app_state: ResMut<State<AppState>>,
+    mut typing_mode: ResMut<TypingMode>,
+    review: Res<Review>,
+    word_list: Res<WordList>,
+    table: Res<GlyphTable>,
+    mix: Res<PunctuationMix>,
+    set: Res<CharacterSet>,
+    decks: Res<Decks>,
+    mut queue: ResMut<GlyphQueue>,
+    mut sentence: ResMut<Sentence>,
 ) {
+    let word_list = set.filter(&word_list.words, &table, &decks);
+    // passages get punctuation, unless it would have to be dictated
+    let passage = || match mix.enabled {
+        true => mix_punctuation(&word_list),
+        false => word_list.clone(),
+    };
+
     for (interaction, action) in interaction_query.iter() {
         if *interaction == Interaction::Clicked {
+            match action {
+                ButtonAction::Play
+                | ButtonAction::Tutorial
+                | ButtonAction::Punctuation
+                | ButtonAction::Drill => *typing_mode = TypingMode::Normal,
+                ButtonAction::Dictation => *typing_mode = TypingMode::Dictation,
+                ButtonAction::Sentence => *typing_mode = TypingMode::Sentence,
+                ButtonAction::Sprint => *typing_mode = TypingMode::Normal,
+                _ => {}
+            }
+
+            match action {
+                ButtonAction::Tutorial => {
+                    *queue = GlyphQueue {
+                        glyphs: passage(),
+                        index: 0,
+                    }
+                }
+                ButtonAction::Dictation => {
+                    *queue = GlyphQueue {
+                        glyphs: word_list.clone(),
+                        index: 0,
+                    }
+                }
+                ButtonAction::Sentence => {
+                    sentence.queue(sentences(&word_list, &set, &table, &decks), &mut queue)
+                }
+                ButtonAction::Punctuation => {
+                    *queue = GlyphQueue {
+                        glyphs: punctuation_glyphs(),
+                        index: 0,
+                    }
+                }
+                // sprint through the whole set unless a word list was imported
+                ButtonAction::Sprint if word_list.is_empty() => {
+                    *queue = GlyphQueue {
+                        glyphs: set.glyphs(&table, &decks),
+                        index: 0,
+                    }
+                }
+                ButtonAction::Sprint => {
+                    *queue = GlyphQueue {
+                        glyphs: passage(),
+                        index: 0,
+                    }
+                }
+                ButtonAction::Drill => start_drill(&review, &mut queue),
+                _ => {}
+            }
+
             let state = match action {
                 ButtonAction::Play => AppState::Battle,
-                ButtonAction::Tutorial => AppState::Practice,
+                ButtonAction::Tutorial
+                | ButtonAction::Dictation
+                | ButtonAction::Sentence
+                | ButtonAction::Punctuation
+                | ButtonAction::Drill => AppState::Practice,
+                ButtonAction::Sprint => AppState::Sprint,
+                ButtonAction::Stats => AppState::Stats,
                 ButtonAction::Settings => AppState::Settings,
+                ButtonAction::Review => AppState::Review,
                 ButtonAction::Back => AppState::Menu,
+                // handled by export_stats
+                ButtonAction::Export => continue,
             };
             app_state.set(state).unwrap();
         }
     }
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn value_system(
     mut interaction_query: Query<(&Interaction, &mut BackgroundColor, &ValueAction), With<Button>>,
     volume: Res<AudioVolume>,
+    sprint: Res<Sprint>,
+    scheme: Res<Scheme>,
+    overlay: Res<KeyboardOverlay>,
+    layout: Res<KeyLayout>,
+    mix: Res<PunctuationMix>,
+    guide: Res<FingerGuide>,
+    gloss: Res<GlossDisplay>,
+    set: Res<CharacterSet>,
+    fuzzy: Res<FuzzyPinyin>,
 ) {
     for (interaction, mut color, action) in interaction_query.iter_mut() {
         match *interaction {
@@ -735,25 +1685,94 @@ fn value_system(
                             *color = SETTING_ACTIVE_COLOR.into();
                         }
                     }
+                    ValueAction::PronunciationVolume(v) => {
+                        if volume.pronunciation >= *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::SprintDuration(v) => {
+                        if sprint.duration == *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::Scheme(v) => {
+                        if *scheme == *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::Keyboard(v) => {
+                        if overlay.enabled == *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::KeyLayout(v) => {
+                        if *layout == *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::PunctuationMix(v) => {
+                        if mix.enabled == *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::FingerGuide(v) => {
+                        if guide.enabled == *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::GlossDisplay(v) => {
+                        if gloss.enabled == *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::CharacterSet(v) => {
+                        if *set == *v {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
+                    ValueAction::FuzzyRule(v) => {
+                        if fuzzy.rules.contains(v) {
+                            *color = SETTING_ACTIVE_COLOR.into();
+                        }
+                    }
                 };
             }
         }
     }
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn value_action(
     interaction_query: Query<(&Interaction, &ValueAction), (Changed<Interaction>, With<Button>)>,
     mut volume: ResMut<AudioVolume>,
-    audio: Res<Audio>,
+    mut sprint: ResMut<Sprint>,
+    mut scheme: ResMut<Scheme>,
+    mut overlay: ResMut<KeyboardOverlay>,
+    mut layout: ResMut<KeyLayout>,
+    mut mix: ResMut<PunctuationMix>,
+    mut guide: ResMut<FingerGuide>,
+    mut gloss: ResMut<GlossDisplay>,
+    mut set: ResMut<CharacterSet>,
+    mut fuzzy: ResMut<FuzzyPinyin>,
 ) {
     for (interaction, action) in interaction_query.iter() {
         if *interaction == Interaction::Clicked {
             match action {
                 ValueAction::AudioVolume(v) => volume.effects = *v,
-                ValueAction::MusicVolume(v) => {
-                    volume.music = *v;
-                    audio.set_volume(volume.music.into());
+                ValueAction::MusicVolume(v) => volume.music = *v,
+                ValueAction::PronunciationVolume(v) => volume.pronunciation = *v,
+                ValueAction::SprintDuration(v) => sprint.duration = *v,
+                ValueAction::Scheme(v) => *scheme = *v,
+                ValueAction::Keyboard(v) => overlay.enabled = *v,
+                ValueAction::KeyLayout(v) => *layout = *v,
+                ValueAction::PunctuationMix(v) => mix.enabled = *v,
+                ValueAction::FingerGuide(v) => guide.enabled = *v,
+                ValueAction::GlossDisplay(v) => gloss.enabled = *v,
+                ValueAction::CharacterSet(v) => *set = *v,
+                ValueAction::FuzzyRule(v) => {
+                    if !fuzzy.rules.remove(v) {
+                        fuzzy.rules.insert(*v);
+                    }
                 }
             }
         }
@@ -0,0 +1,41 @@
+use crate::{constants::*, AudioVolume, MusicTrack};
+use bevy::prelude::*;
+use bevy_kira_audio::{Audio, AudioControl};
+
+/// Plays the [`MusicTrack`] picked by each state, at the music volume of the settings.
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(play_music)
+            .add_system(music_volume.after(play_music));
+    }
+}
+
+fn play_music(
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    volume: Res<AudioVolume>,
+    music_track: Res<MusicTrack>,
+) {
+    if !music_track.is_changed() || music_track.0.is_empty() {
+        return;
+    }
+
+    // the game music is played a bit faster
+    let playback_rate = if music_track.0 == GAME_MUSIC {
+        1.2
+    } else {
+        1.0
+    };
+    audio.stop();
+    audio.set_playback_rate(playback_rate);
+    audio.set_volume(volume.music.into());
+    audio.play(asset_server.load(music_track.0)).looped();
+}
+
+fn music_volume(audio: Res<Audio>, volume: Res<AudioVolume>) {
+    if volume.is_changed() {
+        audio.set_volume(volume.music.into());
+    }
+}
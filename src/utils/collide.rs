@@ -5,6 +5,7 @@ use core::f32;
 pub struct Penetration {
     pub normal: Vec2,
     pub location: Vec2,
+    /// Distance to move `a` along the normal to separate the colliders, always positive.
     pub depth: f32,
 }
 
@@ -38,17 +39,133 @@ impl Hit {
     }
 }
 
+/// Shape of a collider, centered on its position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    /// Axis-aligned box of the given size.
+    Aabb(Vec2),
+    Circle(f32),
+    /// Horizontal segment swept by a circle, like a rounded paddle.
+    Capsule {
+        half_length: f32,
+        radius: f32,
+    },
+}
+
+impl Shape {
+    /// Size of the bounding box.
+    pub fn size(&self) -> Vec2 {
+        match *self {
+            Shape::Aabb(size) => size,
+            Shape::Circle(radius) => Vec2::splat(2.0 * radius),
+            Shape::Capsule {
+                half_length,
+                radius,
+            } => Vec2::new(2.0 * (half_length + radius), 2.0 * radius),
+        }
+    }
+
+    /// Whether a point, relative to the center, is inside the shape.
+    pub fn contains(&self, point: Vec2) -> bool {
+        match *self {
+            Shape::Aabb(size) => point.abs().cmple(size / 2.0).all(),
+            Shape::Circle(radius) => point.length_squared() <= radius * radius,
+            Shape::Capsule {
+                half_length,
+                radius,
+            } => {
+                let nearest = Vec2::new(point.x.clamp(-half_length, half_length), 0.0);
+                point.distance_squared(nearest) <= radius * radius
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Collider {
     pub previous_position: Vec2,
     pub position: Vec2,
-    pub size: Vec2,
+    pub shape: Shape,
 }
 
 impl Collider {
     pub fn delta(&self) -> Vec2 {
         self.position - self.previous_position
     }
+
+    /// Bounding box of the collider over its whole motion, from its previous position to the current one.
+    pub fn swept_bounds(&self) -> (Vec2, Vec2) {
+        let extent = self.shape.size() / 2.0;
+        (
+            self.previous_position.min(self.position) - extent,
+            self.previous_position.max(self.position) + extent,
+        )
+    }
+
+    fn min(&self) -> Vec2 {
+        self.position - self.shape.size() / 2.0
+    }
+
+    fn max(&self) -> Vec2 {
+        self.position + self.shape.size() / 2.0
+    }
+
+    /// Replaces a capsule by the circle of it nearest to another collider,
+    /// so capsules are tested like circles. Other shapes are returned as they are.
+    fn nearest_circle(&self, other: &Collider) -> Collider {
+        match self.shape {
+            Shape::Capsule {
+                half_length,
+                radius,
+            } => {
+                let offset = (other.position.x - self.position.x).clamp(-half_length, half_length);
+                Collider {
+                    previous_position: self.previous_position + offset * Vec2::X,
+                    position: self.position + offset * Vec2::X,
+                    shape: Shape::Circle(radius),
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+}
+
+fn flip_penetration(penetration: Penetration) -> Penetration {
+    Penetration {
+        normal: -penetration.normal,
+        ..penetration
+    }
+}
+
+fn flip_cast(cast: Cast) -> Cast {
+    Cast {
+        normal: -cast.normal,
+        ..cast
+    }
+}
+
+/// Sweep and prune: pairs of indices of the bounding boxes that overlap,
+/// found by sorting the boxes along the x axis and only comparing those whose spans meet.
+pub fn overlapping_pairs(bounds: &[(Vec2, Vec2)]) -> Vec<[usize; 2]> {
+    let mut order: Vec<usize> = (0..bounds.len()).collect();
+    order.sort_by(|&a, &b| bounds[a].0.x.total_cmp(&bounds[b].0.x));
+
+    let mut pairs = vec![];
+    let mut active: Vec<usize> = vec![];
+    for index in order {
+        let (min, max) = bounds[index];
+        active.retain(|&other| bounds[other].1.x >= min.x);
+        for &other in &active {
+            let (other_min, other_max) = bounds[other];
+            if min.y <= other_max.y && other_min.y <= max.y {
+                pairs.push([other.min(index), other.max(index)]);
+            }
+        }
+        active.push(index);
+    }
+
+    pairs.sort_unstable();
+    pairs
 }
 
 fn intersection(a_min: Vec2, a_max: Vec2, b_min: Vec2, b_max: Vec2) -> Vec2 {
@@ -58,12 +175,9 @@ fn intersection(a_min: Vec2, a_max: Vec2, b_min: Vec2, b_max: Vec2) -> Vec2 {
 }
 
 /// Axis-aligned bounding box collision with "side" detection
-fn penetrate(a: &Collider, b: &Collider) -> Option<Penetration> {
-    let a_min = a.position - a.size / 2.0;
-    let a_max = a.position + a.size / 2.0;
-
-    let b_min = b.position - b.size / 2.0;
-    let b_max = b.position + b.size / 2.0;
+fn penetrate_aabb(a: &Collider, b: &Collider) -> Option<Penetration> {
+    let (a_min, a_max) = (a.min(), a.max());
+    let (b_min, b_max) = (b.min(), b.max());
 
     // check to see if the two rectangles are intersecting
     if a_min.x < b_max.x && a_max.x > b_min.x && a_min.y < b_max.y && a_max.y > b_min.y {
@@ -74,7 +188,7 @@ fn penetrate(a: &Collider, b: &Collider) -> Option<Penetration> {
             Some(Penetration {
                 location,
                 normal: -Vec2::X,
-                depth: b_min.x - a_max.x,
+                depth: a_max.x - b_min.x,
             })
         } else if a_min.x > b_min.x && a_min.x < b_max.x && a_max.x > b_max.x {
             Some(Penetration {
@@ -91,7 +205,7 @@ fn penetrate(a: &Collider, b: &Collider) -> Option<Penetration> {
             Some(Penetration {
                 location,
                 normal: -Vec2::Y,
-                depth: b_min.y - a_max.y,
+                depth: a_max.y - b_min.y,
             })
         } else if a_min.y > b_min.y && a_min.y < b_max.y && a_max.y > b_max.y {
             Some(Penetration {
@@ -106,7 +220,7 @@ fn penetrate(a: &Collider, b: &Collider) -> Option<Penetration> {
         // if we had an "x" and a "y" collision, pick the "primary" side using penetration depth
         match (x, y) {
             (Some(x), Some(y)) => {
-                if y.depth.abs() < x.depth.abs() {
+                if y.depth < x.depth {
                     Some(y)
                 } else {
                     Some(x)
@@ -121,20 +235,166 @@ fn penetrate(a: &Collider, b: &Collider) -> Option<Penetration> {
     }
 }
 
-fn cast(a: &Collider, b: &Collider) -> Option<Cast> {
+/// Circle `a` overlapping box `b`, pushed out through the nearest side if its center is inside.
+fn penetrate_circle_aabb(a: &Collider, radius: f32, b: &Collider) -> Option<Penetration> {
+    let (b_min, b_max) = (b.min(), b.max());
+    let location = a.position.clamp(b_min, b_max);
+    let offset = a.position - location;
+
+    if offset != Vec2::ZERO {
+        let distance = offset.length();
+        return (distance < radius).then(|| Penetration {
+            normal: offset / distance,
+            location,
+            depth: radius - distance,
+        });
+    }
+
+    let sides = [
+        (a.position.x - b_min.x, -Vec2::X),
+        (b_max.x - a.position.x, Vec2::X),
+        (a.position.y - b_min.y, -Vec2::Y),
+        (b_max.y - a.position.y, Vec2::Y),
+    ];
+    let (distance, normal) = sides
+        .into_iter()
+        .min_by(|(x, _), (y, _)| x.total_cmp(y))
+        .unwrap();
+    Some(Penetration {
+        normal,
+        location,
+        depth: distance + radius,
+    })
+}
+
+fn penetrate_circles(
+    a: &Collider,
+    a_radius: f32,
+    b: &Collider,
+    b_radius: f32,
+) -> Option<Penetration> {
+    let offset = a.position - b.position;
+    let distance = offset.length();
+    if distance >= a_radius + b_radius {
+        return None;
+    }
+
+    // concentric circles are pushed apart upwards
+    let normal = if distance > f32::EPSILON {
+        offset / distance
+    } else {
+        Vec2::Y
+    };
+    Some(Penetration {
+        normal,
+        location: b.position + normal * b_radius,
+        depth: a_radius + b_radius - distance,
+    })
+}
+
+/// Overlap of two colliders, with the normal pointing from `b` towards `a`.
+fn penetrate(a: &Collider, b: &Collider) -> Option<Penetration> {
+    let (a, b) = (a.nearest_circle(b), b.nearest_circle(a));
+    match (a.shape, b.shape) {
+        (Shape::Circle(a_radius), Shape::Circle(b_radius)) => {
+            penetrate_circles(&a, a_radius, &b, b_radius)
+        }
+        (Shape::Circle(radius), _) => penetrate_circle_aabb(&a, radius, &b),
+        (_, Shape::Circle(radius)) => penetrate_circle_aabb(&b, radius, &a).map(flip_penetration),
+        _ => penetrate_aabb(&a, &b),
+    }
+}
+
+/// Times a ray from `origin` along `delta` enters and leaves a circle.
+fn ray_circle(origin: Vec2, delta: Vec2, center: Vec2, radius: f32) -> Option<(f32, f32)> {
+    let offset = origin - center;
+    let a = delta.length_squared();
+    let b = 2.0 * offset.dot(delta);
+    let c = offset.length_squared() - radius * radius;
+    let discriminant = b * b - 4.0 * a * c;
+    if a < f32::EPSILON || discriminant < 0.0 {
+        return None;
+    }
+
+    let root = discriminant.sqrt();
+    Some(((-b - root) / (2.0 * a), (-b + root) / (2.0 * a)))
+}
+
+/// Circle `a` swept against circle `b`, the motion of both taken relative to `b` where it started.
+fn cast_circles(a: &Collider, a_radius: f32, b: &Collider, b_radius: f32) -> Option<Cast> {
+    let origin = a.previous_position;
+    let delta = a.delta() - b.delta();
+    let center = b.previous_position;
+    let (near_time, far_time) = ray_circle(origin, delta, center, a_radius + b_radius)?;
+    if near_time >= 1.0 || far_time <= 0.0 || near_time < 0.0 {
+        return None;
+    }
+
+    let normal = (origin + delta * near_time - center).normalize_or_zero();
+    Some(Cast {
+        normal,
+        location: center + near_time * b.delta() + normal * b_radius,
+        near_time,
+        far_time,
+    })
+}
+
+/// Circle `a` swept against box `b`: the box grown by the radius, with rounded corners.
+fn cast_circle_aabb(a: &Collider, radius: f32, b: &Collider) -> Option<Cast> {
+    let mut cast = cast_aabb(a, Vec2::splat(radius), b)?;
+    let (b_min, b_max) = (
+        b.previous_position - b.shape.size() / 2.0,
+        b.previous_position + b.shape.size() / 2.0,
+    );
     let origin = a.previous_position;
     let delta = a.delta() - b.delta();
-    let padding = a.size / 2.0;
+    let center = origin + delta * cast.near_time;
 
-    let sign = delta.signum();
-    let scale = delta.recip();
+    // past a corner on both axes the circle hits the corner itself, if at all
+    let corner = center.clamp(b_min, b_max);
+    if (center.x < b_min.x || center.x > b_max.x) && (center.y < b_min.y || center.y > b_max.y) {
+        let (near_time, far_time) = ray_circle(origin, delta, corner, radius)?;
+        if near_time >= 1.0 || far_time <= 0.0 || near_time < 0.0 {
+            return None;
+        }
+        cast.near_time = near_time;
+        cast.far_time = far_time;
+        cast.normal = (origin + delta * near_time - corner).normalize_or_zero();
+    }
+
+    cast.location = a.previous_position + a.delta() * cast.near_time - cast.normal * radius;
+    Some(cast)
+}
 
-    if !scale.is_finite() {
+/// Box `a` swept against box `b`, `a` grown by `padding` on each side,
+/// the motion of both taken relative to `b` where it started.
+fn cast_aabb(a: &Collider, padding: Vec2, b: &Collider) -> Option<Cast> {
+    let origin = a.previous_position;
+    let center = b.previous_position;
+    let delta = a.delta() - b.delta();
+    let b_size = b.shape.size();
+
+    if delta == Vec2::ZERO {
         return None;
     }
 
-    let near_time = (b.position - sign * (b.size / 2.0 + padding) - origin) * scale;
-    let far_time = (b.position + sign * (b.size / 2.0 + padding) - origin) * scale;
+    let sign = delta.signum();
+    let scale = delta.recip();
+    let extent = b_size / 2.0 + padding;
+
+    let mut near_time = (center - sign * extent - origin) * scale;
+    let mut far_time = (center + sign * extent - origin) * scale;
+
+    // moving along a single axis, the other one has to overlap all the time
+    for axis in 0..2 {
+        if delta[axis] == 0.0 {
+            if (origin[axis] - center[axis]).abs() >= extent[axis] {
+                return None;
+            }
+            near_time[axis] = f32::NEG_INFINITY;
+            far_time[axis] = f32::INFINITY;
+        }
+    }
 
     if near_time.x > far_time.y || near_time.y > far_time.x {
         return None;
@@ -151,11 +411,11 @@ fn cast(a: &Collider, b: &Collider) -> Option<Cast> {
         return None;
     }
 
-    let a_min = a.position - a.size / 2.0 + near_time * a.delta();
-    let a_max = a.position + a.size / 2.0 + near_time * a.delta();
+    let a_min = origin - padding + near_time * a.delta();
+    let a_max = origin + padding + near_time * a.delta();
 
-    let b_min = b.position - b.size / 2.0 + near_time * b.delta();
-    let b_max = b.position + b.size / 2.0 + near_time * b.delta();
+    let b_min = center - b_size / 2.0 + near_time * b.delta();
+    let b_max = center + b_size / 2.0 + near_time * b.delta();
 
     let location = intersection(a_min, a_max, b_min, b_max);
 
@@ -167,7 +427,21 @@ fn cast(a: &Collider, b: &Collider) -> Option<Cast> {
     })
 }
 
-/// Axis-aligned bounding box continuous collision.
+/// Swept test of `a` against `b`, with the normal pointing from `b` towards `a`.
+fn cast(a: &Collider, b: &Collider) -> Option<Cast> {
+    let (a, b) = (a.nearest_circle(b), b.nearest_circle(a));
+    match (a.shape, b.shape) {
+        (Shape::Circle(a_radius), Shape::Circle(b_radius)) => {
+            cast_circles(&a, a_radius, &b, b_radius)
+        }
+        (Shape::Circle(radius), _) => cast_circle_aabb(&a, radius, &b),
+        // sweep the circle backwards through the box
+        (_, Shape::Circle(radius)) => cast_circle_aabb(&b, radius, &a).map(flip_cast),
+        _ => cast_aabb(&a, a.shape.size() / 2.0, &b),
+    }
+}
+
+/// Continuous collision of two shapes.
 /// Returns collision time information
 pub fn collide(a: &Collider, b: &Collider) -> Option<Hit> {
     // check if already overlapped
@@ -189,3 +463,190 @@ pub fn collide(a: &Collider, b: &Collider) -> Option<Hit> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn still(position: Vec2, shape: Shape) -> Collider {
+        Collider {
+            previous_position: position,
+            position,
+            shape,
+        }
+    }
+
+    fn moving(from: Vec2, to: Vec2, shape: Shape) -> Collider {
+        Collider {
+            previous_position: from,
+            position: to,
+            shape,
+        }
+    }
+
+    const BOX: Shape = Shape::Aabb(Vec2::new(100.0, 20.0));
+
+    #[test]
+    fn depths_are_positive() {
+        let wall = still(Vec2::ZERO, BOX);
+        let aabb = penetrate(
+            &still(Vec2::new(0.0, 25.0), Shape::Aabb(Vec2::splat(40.0))),
+            &wall,
+        )
+        .unwrap();
+        assert_eq!(aabb.normal, Vec2::Y);
+        assert!((aabb.depth - 5.0).abs() < 1e-4);
+
+        let circle = penetrate(&still(Vec2::new(0.0, 15.0), Shape::Circle(10.0)), &wall).unwrap();
+        assert_eq!(circle.normal, Vec2::Y);
+        assert!((circle.depth - 5.0).abs() < 1e-4);
+
+        let below = penetrate(&still(Vec2::new(0.0, -25.0), Shape::Circle(10.0)), &wall);
+        assert!(below.is_none());
+    }
+
+    #[test]
+    fn circle_inside_box_leaves_through_nearest_side() {
+        let hit = penetrate(
+            &still(Vec2::new(45.0, 0.0), Shape::Circle(10.0)),
+            &still(Vec2::ZERO, BOX),
+        )
+        .unwrap();
+        assert_eq!(hit.normal, Vec2::X);
+        assert!((hit.depth - 15.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn circle_on_corner_is_pushed_diagonally() {
+        let hit = penetrate(
+            &still(Vec2::new(55.0, 15.0), Shape::Circle(10.0)),
+            &still(Vec2::ZERO, BOX),
+        )
+        .unwrap();
+        let expected = Vec2::new(5.0, 5.0);
+        assert!(hit.normal.abs_diff_eq(expected.normalize(), 1e-4));
+        assert!((hit.depth - (10.0 - expected.length())).abs() < 1e-4);
+        assert_eq!(hit.location, Vec2::new(50.0, 10.0));
+
+        // the box flipped around has the opposite normal
+        let flipped = penetrate(
+            &still(Vec2::ZERO, BOX),
+            &still(Vec2::new(55.0, 15.0), Shape::Circle(10.0)),
+        )
+        .unwrap();
+        assert!(flipped.normal.abs_diff_eq(-hit.normal, 1e-4));
+        assert!((flipped.depth - hit.depth).abs() < 1e-4);
+    }
+
+    #[test]
+    fn concentric_circles_separate_upwards() {
+        let hit = penetrate(
+            &still(Vec2::ONE, Shape::Circle(10.0)),
+            &still(Vec2::ONE, Shape::Circle(5.0)),
+        )
+        .unwrap();
+        assert_eq!(hit.normal, Vec2::Y);
+        assert!((hit.depth - 15.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn zero_delta_does_not_cast() {
+        let wall = still(Vec2::ZERO, BOX);
+        assert!(cast(&still(Vec2::new(0.0, 40.0), Shape::Circle(10.0)), &wall).is_none());
+        assert!(cast(
+            &still(Vec2::new(0.0, 40.0), Shape::Aabb(Vec2::splat(10.0))),
+            &wall
+        )
+        .is_none());
+        assert!(ray_circle(Vec2::ZERO, Vec2::ZERO, Vec2::X, 1.0).is_none());
+    }
+
+    #[test]
+    fn cast_hits_side_and_corner() {
+        let wall = still(Vec2::ZERO, BOX);
+        let side = cast(
+            &moving(
+                Vec2::new(0.0, 60.0),
+                Vec2::new(0.0, 0.0),
+                Shape::Circle(10.0),
+            ),
+            &wall,
+        )
+        .unwrap();
+        assert_eq!(side.normal, Vec2::Y);
+        assert!((side.near_time - 40.0 / 60.0).abs() < 1e-4);
+        assert!(side.location.abs_diff_eq(Vec2::new(0.0, 10.0), 1e-4));
+
+        // aimed diagonally at the corner, the rounded corner is hit later than the grown box
+        let corner = cast(
+            &moving(
+                Vec2::new(80.0, 40.0),
+                Vec2::new(20.0, -20.0),
+                Shape::Circle(10.0),
+            ),
+            &wall,
+        )
+        .unwrap();
+        assert!(corner.normal.abs_diff_eq(Vec2::ONE.normalize(), 1e-4));
+        let center = Vec2::new(80.0, 40.0) + Vec2::new(-60.0, -60.0) * corner.near_time;
+        assert!((center.distance(Vec2::new(50.0, 10.0)) - 10.0).abs() < 1e-3);
+
+        // passing beside the corner misses it, though it crosses the grown box
+        let miss = cast(
+            &moving(
+                Vec2::new(70.0, 30.0),
+                Vec2::new(90.0, 10.0),
+                Shape::Circle(10.0),
+            ),
+            &wall,
+        );
+        assert!(miss.is_none());
+    }
+
+    #[test]
+    fn ray_enters_and_leaves_circle() {
+        let (near, far) = ray_circle(
+            Vec2::new(-20.0, 0.0),
+            Vec2::new(40.0, 0.0),
+            Vec2::ZERO,
+            10.0,
+        )
+        .unwrap();
+        assert!((near - 0.25).abs() < 1e-4);
+        assert!((far - 0.75).abs() < 1e-4);
+        assert!(ray_circle(
+            Vec2::new(-20.0, 20.0),
+            Vec2::new(40.0, 0.0),
+            Vec2::ZERO,
+            10.0
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn capsule_is_tested_as_nearest_circle() {
+        let paddle = still(
+            Vec2::ZERO,
+            Shape::Capsule {
+                half_length: 40.0,
+                radius: 8.0,
+            },
+        );
+        let ball = still(Vec2::new(30.0, 15.0), Shape::Circle(10.0));
+        let nearest = paddle.nearest_circle(&ball);
+        assert_eq!(nearest.position, Vec2::new(30.0, 0.0));
+        assert_eq!(nearest.shape, Shape::Circle(8.0));
+
+        // beyond the end the ball meets the rounded cap
+        let far = still(Vec2::new(60.0, 0.0), Shape::Circle(10.0));
+        assert_eq!(paddle.nearest_circle(&far).position, Vec2::new(40.0, 0.0));
+
+        let hit = penetrate(&ball, &paddle).unwrap();
+        assert_eq!(hit.normal, Vec2::Y);
+        assert!((hit.depth - 3.0).abs() < 1e-4);
+
+        let cap = penetrate(&still(Vec2::new(55.0, 0.0), Shape::Circle(10.0)), &paddle).unwrap();
+        assert_eq!(cap.normal, Vec2::X);
+        assert!((cap.depth - 3.0).abs() < 1e-4);
+    }
+}
@@ -19,10 +19,12 @@ impl_interpolation!(Vec2);
 impl_interpolation!(Vec3);
 impl_interpolation!(Vec4);
 
+#[cfg(feature = "audio")]
 pub trait Intermediate {
     fn intermediate(self, begin: Self, end: Self) -> Self;
 }
 
+#[cfg(feature = "audio")]
 impl Intermediate for f32 {
     fn intermediate(self, begin: Self, end: Self) -> Self {
         (self - begin) / (end - begin)
@@ -1,6 +1,7 @@
 mod collide;
 mod damp;
 mod interpolation;
+mod storage;
 
 use crate::AppState;
 use bevy::prelude::*;
@@ -8,6 +9,7 @@ use bevy::prelude::*;
 pub use collide::*;
 pub use damp::*;
 pub use interpolation::*;
+pub use storage::*;
 
 pub fn cleanup_system<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
     for entity in query.iter() {
@@ -0,0 +1,16 @@
+use crate::constants::DATA_DIR_NAME;
+use std::{env, path::PathBuf};
+
+/// Directory for files kept between sessions, e.g. `~/.local/share/bounce-up` on Linux.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+    base.map(|base| base.join(DATA_DIR_NAME))
+}
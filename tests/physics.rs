@@ -0,0 +1,122 @@
+//! Physics steps of a few bodies, without the rest of the game.
+
+use bevy::{ecs::event::Events, prelude::*};
+use bounce_up::{
+    physics_systems, CollisionEvent, ContactEvent, Motion, PhysicsLayers, RigidBody, Shape,
+    TimeScale,
+};
+
+const STEPS: usize = 120;
+const WALL_SIZE: Vec2 = Vec2::new(400.0, 32.0);
+const BALL_RADIUS: f32 = 16.0;
+const PADDLE_SHAPE: Shape = Shape::Capsule {
+    half_length: 40.0,
+    radius: 16.0,
+};
+/// The paddle is driven down onto the ball, and sideways to rub it.
+const PADDLE_VELOCITY: Vec2 = Vec2::new(20.0, -300.0);
+
+#[derive(Debug)]
+struct State {
+    translation: Vec2,
+    velocity: Vec2,
+    angular_velocity: f32,
+}
+
+fn moving(translation: Vec2, velocity: Vec2, angular_velocity: f32) -> Motion {
+    Motion {
+        translation: translation.extend(0.0),
+        previous_translation: translation.extend(0.0),
+        velocity,
+        angular_velocity,
+    }
+}
+
+/// A spinning ball wedged between a paddle and the wall under it, with the bodies spawned in `order`.
+/// Returns the ball and the paddle at the end.
+fn wedge(order: [usize; 3]) -> [State; 2] {
+    let mut world = World::new();
+    world.init_resource::<TimeScale>();
+    world.init_resource::<Events<CollisionEvent>>();
+    world.init_resource::<Events<ContactEvent>>();
+
+    let mut entities = [None; 3];
+    for index in order {
+        let mut entity = match index {
+            0 => world.spawn((
+                Transform::default(),
+                RigidBody::new(Shape::Aabb(WALL_SIZE), 0.0, 0.9, 0.5),
+                PhysicsLayers::BOUNDARY,
+            )),
+            1 => world.spawn((
+                Transform::default(),
+                RigidBody::new(Shape::Circle(BALL_RADIUS), 1.0, 1.0, 0.5),
+                PhysicsLayers::BALL,
+            )),
+            _ => world.spawn((
+                Transform::default(),
+                RigidBody::new(PADDLE_SHAPE, 3.0, 2.0, 1.0),
+                PhysicsLayers::PLAYER,
+            )),
+        };
+        let ball_y = WALL_SIZE.y / 2.0 + BALL_RADIUS;
+        match index {
+            1 => entity.insert(moving(Vec2::new(0.0, ball_y), Vec2::ZERO, 20.0)),
+            2 => entity.insert(moving(
+                Vec2::new(0.0, ball_y + 2.0 * BALL_RADIUS),
+                PADDLE_VELOCITY,
+                0.0,
+            )),
+            _ => &mut entity,
+        };
+        entities[index] = Some(entity.id());
+    }
+    let [_, ball, paddle] = entities.map(Option::unwrap);
+
+    let mut stage = SystemStage::single_threaded().with_system_set(physics_systems());
+    for _ in 0..STEPS {
+        world.get_mut::<Motion>(paddle).unwrap().velocity = PADDLE_VELOCITY;
+        stage.run(&mut world);
+        world.resource_mut::<Events<CollisionEvent>>().update();
+        world.resource_mut::<Events<ContactEvent>>().update();
+    }
+
+    [ball, paddle].map(|entity| {
+        let motion = world.get::<Motion>(entity).unwrap();
+        State {
+            translation: motion.translation.truncate(),
+            velocity: motion.velocity,
+            angular_velocity: motion.angular_velocity,
+        }
+    })
+}
+
+fn assert_close(a: &State, b: &State, order: [usize; 3]) {
+    assert!(
+        a.translation.abs_diff_eq(b.translation, 1e-3)
+            && a.velocity.abs_diff_eq(b.velocity, 1e-3)
+            && (a.angular_velocity - b.angular_velocity).abs() < 1e-3,
+        "{order:?}: {a:?} != {b:?}"
+    );
+}
+
+#[test]
+fn wedged_ball_does_not_depend_on_spawn_order() {
+    let expected = wedge([0, 1, 2]);
+    for order in [[0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]] {
+        let result = wedge(order);
+        assert_close(&result[0], &expected[0], order);
+        assert_close(&result[1], &expected[1], order);
+    }
+}
+
+#[test]
+fn wedged_ball_rolls_under_paddle() {
+    let [ball, paddle] = wedge([0, 1, 2]);
+    assert!(ball.translation.y > WALL_SIZE.y / 2.0);
+    assert!(ball.translation.y < paddle.translation.y);
+
+    // rolling on the wall, the ball moves at half the speed of the paddle, and spins to match
+    assert!((ball.velocity.x - PADDLE_VELOCITY.x / 2.0).abs() < 1.0);
+    assert!((ball.angular_velocity + ball.velocity.x / BALL_RADIUS).abs() < 0.1);
+}
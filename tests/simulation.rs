@@ -0,0 +1,81 @@
+//! Headless runs of the game logic.
+//!
+//! Run without a window or sound with `cargo test --no-default-features`.
+
+use bevy::prelude::*;
+use bounce_up::{simulate, Glyph, Simulation, SimulationMode, SimulationResult};
+
+const TICKS: usize = 3600;
+
+/// Mouse motion swinging the paddle left and right.
+fn swing() -> Vec<Vec2> {
+    let mut script = vec![Vec2::new(4.0, 0.0); 30];
+    script.extend(vec![Vec2::new(-4.0, 0.0); 30]);
+    script
+}
+
+fn run(mode: SimulationMode, script: Vec<Vec2>) -> SimulationResult {
+    type_codes(mode, script, vec![])
+}
+
+fn type_codes(
+    mode: SimulationMode,
+    script: Vec<Vec2>,
+    typing: Vec<(usize, String)>,
+) -> SimulationResult {
+    simulate(&Simulation {
+        mode,
+        ticks: TICKS,
+        script,
+        typing,
+        glyph: Glyph::from_reading("中", "zhong1"),
+        seed: 7,
+    })
+}
+
+#[test]
+fn same_seed_gives_same_score() {
+    for mode in [
+        SimulationMode::Battle,
+        SimulationMode::Practice,
+        SimulationMode::PracticeSlits,
+    ] {
+        let (a, b) = (run(mode, swing()), run(mode, swing()));
+        assert_eq!(a.ticks, b.ticks, "{mode:?}");
+        assert_eq!(a.score.hits, b.score.hits, "{mode:?}");
+        assert_eq!(a.score.miss, b.score.miss, "{mode:?}");
+        assert_eq!(a.enemy_hp, b.enemy_hp, "{mode:?}");
+    }
+}
+
+#[test]
+fn battle_ends_once_lost() {
+    let result = run(SimulationMode::Battle, swing());
+    assert!(result.ticks < TICKS);
+    assert!(result.score.miss > 0);
+}
+
+#[test]
+fn practice_runs_to_the_end() {
+    for mode in [SimulationMode::Practice, SimulationMode::PracticeSlits] {
+        let result = run(mode, swing());
+        assert_eq!(result.ticks, TICKS, "{mode:?}");
+        assert!(result.score.miss > 0, "{mode:?}");
+    }
+}
+
+#[test]
+fn typed_codes_answer_the_glyph() {
+    // the ball is in play two seconds after it's set, 360 ticks in, and the assist keeps it there
+    let typing = [
+        (400, "zhong "),
+        (500, "zh"),
+        (501, "ong "),
+        (600, "zong "),
+        (700, "zhong "),
+    ]
+    .map(|(tick, text)| (tick, text.to_string()))
+    .to_vec();
+    let result = type_codes(SimulationMode::Practice, vec![], typing);
+    assert_eq!(result.score.answers, 3);
+}